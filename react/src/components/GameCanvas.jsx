@@ -3,31 +3,43 @@ import init, {
   play,
   update,
   resize,
-  init_player,
+  preload_assets,
+  create_team,
+  clear_teams,
+  character_colors,
+  turn_info,
+  leave,
+  draw_time_ms,
+  zoom_camera,
+  set_pointer,
+  clear_pointer,
 } from "../../../rust/pkg/wararar.js";
 
 const GameCanvas = () => {
   const canvasRef = useRef(null);
   const [ready, setReady] = useState(false);
+  const [loading, setLoading] = useState({ loaded: 0, total: 0 });
+  const [loadError, setLoadError] = useState(null);
   const [isPlaying, setIsPlaying] = useState(false);
+  const [turnLabel, setTurnLabel] = useState("");
+  const [teams, setTeams] = useState([
+    { name: "Червоні", color: "red" },
+    { name: "Сині", color: "blue" },
+  ]);
+  const [teamError, setTeamError] = useState(null);
 
-  const keys = useRef({
-    ArrowUp: false,
-    ArrowDown: false,
-    ArrowLeft: false,
-    ArrowRight: false,
-    KeyW: false,
-    KeyS: false,
-    KeyA: false,
-    KeyD: false,
-    Space: false,
-  });
+  // Коди всіх утримуваних клавіш; у дії їх перекладає Rust (див. bind_key)
+  const keys = useRef(new Set());
 
   useEffect(() => {
     const setup = async () => {
       await init();
-      await init_player();
-      setReady(true);
+      try {
+        await preload_assets((loaded, total) => setLoading({ loaded, total }));
+        setReady(true);
+      } catch (e) {
+        setLoadError(String(e));
+      }
     };
 
     setup();
@@ -55,27 +67,50 @@ const GameCanvas = () => {
     window.addEventListener("resize", resizeCanvas);
 
     const handleKeyDown = (e) => {
-      if (keys.current.hasOwnProperty(e.code)) {
-        keys.current[e.code] = true;
-      }
+      keys.current.add(e.code);
     };
 
     const handleKeyUp = (e) => {
-      if (keys.current.hasOwnProperty(e.code)) {
-        keys.current[e.code] = false;
-      }
+      keys.current.delete(e.code);
+    };
+
+    const handleBlur = () => {
+      keys.current.clear();
+    };
+
+    const handleWheel = (e) => {
+      e.preventDefault();
+      zoom_camera(e.offsetX, e.offsetY, e.deltaY);
+    };
+
+    const handleMouseMove = (e) => {
+      set_pointer(e.offsetX, e.offsetY);
     };
 
     window.addEventListener("keydown", handleKeyDown);
     window.addEventListener("keyup", handleKeyUp);
+    window.addEventListener("blur", handleBlur);
+    canvasRef.current?.addEventListener("wheel", handleWheel, { passive: false });
+    canvasRef.current?.addEventListener("mousemove", handleMouseMove);
+    canvasRef.current?.addEventListener("mouseleave", clear_pointer);
+
+    let frameId;
+    const frame = (timestamp) => {
+      update(Array.from(keys.current), timestamp);
+
+      const info = turn_info();
+      if (info) {
+        const phase = info.retreating ? " (відступ)" : "";
+        setTurnLabel(
+          `Хід: ${info.team_name}${phase} — ${Math.ceil(info.seconds_left)} с` +
+            ` · кадр ${draw_time_ms().toFixed(1)} мс`
+        );
+        info.free();
+      }
 
-    const interval = setInterval(() => {
-      const pressedKeysArray = Object.entries(keys.current)
-        .filter(([_, pressed]) => pressed)
-        .map(([key]) => key);
-
-      update(pressedKeysArray);
-    }, 16);
+      frameId = requestAnimationFrame(frame);
+    };
+    frameId = requestAnimationFrame(frame);
 
     try {
       play();
@@ -84,29 +119,78 @@ const GameCanvas = () => {
     }
 
     return () => {
-      clearInterval(interval);
+      leave();
+      cancelAnimationFrame(frameId);
       window.removeEventListener("resize", resizeCanvas);
       window.removeEventListener("keydown", handleKeyDown);
       window.removeEventListener("keyup", handleKeyUp);
+      window.removeEventListener("blur", handleBlur);
+      canvasRef.current?.removeEventListener("wheel", handleWheel);
+      canvasRef.current?.removeEventListener("mousemove", handleMouseMove);
+      canvasRef.current?.removeEventListener("mouseleave", clear_pointer);
     };
   }, [ready, isPlaying]);
 
+  const updateTeam = (index, field, value) => {
+    setTeams(teams.map((t, i) => (i === index ? { ...t, [field]: value } : t)));
+  };
+
   const handlePlayClick = () => {
+    // Колір у матчі не повторюється — create_team відмовить, якщо він зайнятий
+    clear_teams();
+    try {
+      teams.forEach((t) => create_team(t.name, t.color));
+    } catch (e) {
+      setTeamError(String(e));
+      return;
+    }
+    setTeamError(null);
     setIsPlaying(true);
   };
 
   return (
     <div style={{ textAlign: "center" }}>
-      {!ready ? (
-        <p>Loading WASM...</p>
+      {loadError ? (
+        <p style={{ color: "red" }}>Не вдалося завантажити гру: {loadError}</p>
+      ) : !ready ? (
+        <>
+          <p>
+            Завантаження… {loading.loaded}/{loading.total}
+          </p>
+          <progress value={loading.loaded} max={loading.total || 1} />
+        </>
       ) : !isPlaying ? (
-        <button onClick={handlePlayClick}>Play</button>
+        <>
+          {teams.map((team, i) => (
+            <div key={i}>
+              <input
+                value={team.name}
+                onChange={(e) => updateTeam(i, "name", e.target.value)}
+              />
+              <select
+                value={team.color}
+                onChange={(e) => updateTeam(i, "color", e.target.value)}
+              >
+                {character_colors().map((color) => (
+                  <option key={color} value={color}>
+                    {color}
+                  </option>
+                ))}
+              </select>
+            </div>
+          ))}
+          {teamError && <p style={{ color: "red" }}>{teamError}</p>}
+          <button onClick={handlePlayClick}>Play</button>
+        </>
       ) : (
-        <canvas
-          ref={canvasRef}
-          id="mycanvas"
-          style={{ border: "1px solid black", margin: "20px" }}
-        />
+        <>
+          <p>{turnLabel}</p>
+          <canvas
+            ref={canvasRef}
+            id="mycanvas"
+            style={{ border: "1px solid black", margin: "20px" }}
+          />
+        </>
       )}
     </div>
   );
@@ -0,0 +1,245 @@
+//! Анімації зі спрайтшитів, описані даними: набір кліпів (рядок аркуша,
+//! кадри, швидкість, як програвати, події на кадрах) і переходи між ними за
+//! прапорцями стану сутності. Щоб додати анімацію, досить дописати кліп і
+//! перехід в опис, а не гілку в Rust.
+//!
+//! Переходи перевіряються по черзі, спрацьовує перший, у якого виконані всі
+//! `when` і жоден з `unless`. Поки неповторюваний кліп не дограв, його
+//! переривають лише переходи з `interrupt: true`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::render::{Rect, Renderer};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlayMode {
+    #[default]
+    Loop,
+    /// Один раз, далі — за переходами; якщо жоден не підійшов, стоїть на останньому кадрі.
+    Once,
+    /// Один раз і назавжди на останньому кадрі (напр. смерть).
+    HoldLast,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Clip {
+    pub name: String,
+    pub row: usize,
+    #[serde(default)]
+    pub first_frame: u32, // кліп може займати лише частину рядка
+    pub frames: u32,
+    pub fps: f64,
+    #[serde(default)]
+    pub mode: PlayMode,
+    /// Подія, що спрацьовує, коли кліп доходить до кадру (номер у межах кліпу).
+    #[serde(default)]
+    pub events: BTreeMap<u32, String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Transition {
+    pub to: String,
+    #[serde(default)]
+    pub when: Vec<String>,
+    #[serde(default)]
+    pub unless: Vec<String>,
+    #[serde(default)]
+    pub interrupt: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AnimationSet {
+    pub frame_width: f64,
+    pub frame_height: f64,
+    pub initial: String,
+    pub clips: Vec<Clip>,
+    pub transitions: Vec<Transition>,
+}
+
+#[derive(Debug)]
+pub enum AnimationError {
+    Malformed(String),
+    UnknownClip(String),
+    EmptyClip(String),
+}
+
+impl fmt::Display for AnimationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnimationError::Malformed(err) => write!(f, "malformed animation set: {}", err),
+            AnimationError::UnknownClip(name) => write!(f, "unknown animation clip '{}'", name),
+            AnimationError::EmptyClip(name) => {
+                write!(f, "clip '{}' needs at least one frame and fps > 0", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AnimationError {}
+
+impl AnimationSet {
+    pub fn from_json(json: &str) -> Result<Self, AnimationError> {
+        let set: AnimationSet =
+            serde_json::from_str(json).map_err(|e| AnimationError::Malformed(e.to_string()))?;
+        set.validate()?;
+        Ok(set)
+    }
+
+    fn validate(&self) -> Result<(), AnimationError> {
+        if let Some(clip) = self.clips.iter().find(|c| c.frames == 0 || c.fps <= 0.0) {
+            return Err(AnimationError::EmptyClip(clip.name.clone()));
+        }
+        let targets = self.transitions.iter().map(|t| &t.to);
+        for name in std::iter::once(&self.initial).chain(targets) {
+            if self.clip_index(name).is_none() {
+                return Err(AnimationError::UnknownClip(name.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn clip_index(&self, name: &str) -> Option<usize> {
+        self.clips.iter().position(|c| c.name == name)
+    }
+}
+
+/// Програвач набору для одного спрайта.
+pub struct Animation {
+    sprite: String,
+    set: Rc<AnimationSet>,
+    clip: usize,
+    frame: u32, // у межах кліпу
+    timer: f64,
+    finished: bool,
+    entered: bool,       // кліп щойно ввімкнули — подія першого кадру ще попереду
+    events: Vec<String>, // події останнього `update`
+}
+
+impl Animation {
+    pub fn new(sprite: &str, set: Rc<AnimationSet>) -> Self {
+        let clip = set.clip_index(&set.initial).unwrap_or(0);
+        Self {
+            sprite: sprite.to_string(),
+            set,
+            clip,
+            frame: 0,
+            timer: 0.0,
+            finished: false,
+            entered: true,
+            events: Vec::new(),
+        }
+    }
+
+    /// Переходить у перший кліп, чиї умови виконуються для `flags`.
+    pub fn apply_transitions(&mut self, flags: &[&str]) {
+        let locked = self.current_clip().mode != PlayMode::Loop && !self.can_leave();
+        let set = Rc::clone(&self.set);
+        let matches = |t: &&Transition| {
+            (t.interrupt || !locked)
+                && t.when.iter().all(|f| flags.contains(&f.as_str()))
+                && !t.unless.iter().any(|f| flags.contains(&f.as_str()))
+        };
+        if let Some(transition) = set.transitions.iter().find(matches) {
+            self.play(&transition.to);
+        }
+    }
+
+    /// Вмикає кліп з початку; якщо він уже грає — нічого не робить.
+    pub fn play(&mut self, name: &str) {
+        let Some(clip) = self.set.clip_index(name) else {
+            return;
+        };
+        if clip == self.clip {
+            return;
+        }
+        self.clip = clip;
+        self.frame = 0;
+        self.timer = 0.0;
+        self.finished = false;
+        self.entered = true;
+    }
+
+    pub fn update(&mut self, delta_time: f64) {
+        self.events.clear();
+        if std::mem::take(&mut self.entered) {
+            self.fire_event();
+        }
+        let clip = self.current_clip();
+        let (frames, duration, mode) = (clip.frames, 1.0 / clip.fps, clip.mode);
+
+        self.timer += delta_time;
+        while self.timer >= duration && !self.finished {
+            self.timer -= duration;
+            if self.frame + 1 < frames {
+                self.frame += 1;
+            } else if mode == PlayMode::Loop {
+                self.frame = 0;
+            } else {
+                self.finished = true;
+                break;
+            }
+            self.fire_event();
+        }
+    }
+
+    fn fire_event(&mut self) {
+        if let Some(event) = self.current_clip().events.get(&self.frame) {
+            self.events.push(event.clone());
+        }
+    }
+
+    fn can_leave(&self) -> bool {
+        self.finished && self.current_clip().mode == PlayMode::Once
+    }
+
+    pub fn draw(&self, renderer: &dyn Renderer, dest: Rect, flip_x: bool) {
+        let source = Rect::new(
+            self.current_frame() as f64 * self.set.frame_width,
+            self.animation_row() as f64 * self.set.frame_height,
+            self.set.frame_width,
+            self.set.frame_height,
+        );
+        renderer.draw_sprite(&self.sprite, source, dest, flip_x);
+    }
+
+    pub fn sprite(&self) -> &str {
+        &self.sprite
+    }
+
+    /// Інший спрайтшит з тією ж розкладкою кадрів (напр. колір команди).
+    pub fn set_sprite(&mut self, sprite: &str) {
+        self.sprite = sprite.to_string();
+    }
+
+    pub fn current_clip(&self) -> &Clip {
+        &self.set.clips[self.clip]
+    }
+
+    pub fn clip_name(&self) -> &str {
+        &self.current_clip().name
+    }
+
+    pub fn animation_row(&self) -> usize {
+        self.current_clip().row
+    }
+
+    /// Кадр у рядку аркуша.
+    pub fn current_frame(&self) -> u32 {
+        self.current_clip().first_frame + self.frame
+    }
+
+    /// Неповторюваний кліп дійшов до кінця.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Події кадрів, що спрацювали за останній `update`.
+    pub fn events(&self) -> &[String] {
+        &self.events
+    }
+}
@@ -0,0 +1,236 @@
+//! Картинки й звуки гри. Рендерер і звук шукають файл за ключем, а шлях до
+//! нього записаний лише в маніфесті. Клієнт завантажує весь маніфест до
+//! початку гри, показує прогрес і далі бере готове з [`AssetCache`].
+
+use std::collections::HashMap;
+use std::fmt;
+
+pub const JUNGLE_TILES: &str = "tiles/jungle";
+pub const CHARACTER_SHADOW: &str = "character/shadow";
+
+/// Кольори спрайтшитів персонажа Nuclear Leak.
+pub const CHARACTER_COLORS: [&str; 12] = [
+    "black", "blue", "brown", "cyan", "green", "lime", "orange", "pink", "purple", "red", "white",
+    "yellow",
+];
+
+const CHARACTER_DIR: &str = "animations/NuclearLeak_CharacterAnim_1.2";
+
+/// Ключ спрайтшита персонажа кольору `color`.
+pub fn character_key(color: &str) -> String {
+    format!("character/{}", color)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AssetKind {
+    Image,
+    Sound,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AssetEntry {
+    pub key: String,
+    pub path: String,
+    pub kind: AssetKind,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AssetManifest {
+    entries: Vec<AssetEntry>,
+}
+
+impl AssetManifest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Усе, що потрібно грі: тайли землі, персонажі всіх кольорів і їхня тінь.
+    pub fn game() -> Self {
+        let manifest = Self::new()
+            .image(JUNGLE_TILES, "assets/tile_jungle_ground_brown.png")
+            .image(
+                CHARACTER_SHADOW,
+                &format!("{}/character_Shadow_15x4.png", CHARACTER_DIR),
+            );
+        CHARACTER_COLORS.iter().fold(manifest, |manifest, color| {
+            manifest.image(
+                &character_key(color),
+                &format!("{}/character_20x20_{}.png", CHARACTER_DIR, color),
+            )
+        })
+    }
+
+    pub fn image(self, key: &str, path: &str) -> Self {
+        self.with(key, path, AssetKind::Image)
+    }
+
+    pub fn sound(self, key: &str, path: &str) -> Self {
+        self.with(key, path, AssetKind::Sound)
+    }
+
+    fn with(mut self, key: &str, path: &str, kind: AssetKind) -> Self {
+        self.entries.push(AssetEntry {
+            key: key.to_string(),
+            path: path.to_string(),
+            kind,
+        });
+        self
+    }
+
+    pub fn entries(&self) -> &[AssetEntry] {
+        &self.entries
+    }
+
+    pub fn get(&self, key: &str) -> Option<&AssetEntry> {
+        self.entries.iter().find(|e| e.key == key)
+    }
+
+    /// Кожен ключ — один файл.
+    pub fn validate(&self) -> Result<(), AssetError> {
+        for (i, entry) in self.entries.iter().enumerate() {
+            if self.entries[..i].iter().any(|e| e.key == entry.key) {
+                return Err(AssetError::DuplicateKey(entry.key.clone()));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LoadProgress {
+    pub loaded: usize,
+    pub total: usize,
+}
+
+impl LoadProgress {
+    /// Частка від 0 до 1; порожній маніфест уже завантажений.
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.loaded as f64 / self.total as f64
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.loaded >= self.total
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AssetError {
+    DuplicateKey(String),
+    /// Ключа немає в кеші: його не було в маніфесті або він ще не завантажився.
+    NotLoaded(String),
+    /// Під ключем лежить файл іншого типу.
+    WrongKind {
+        key: String,
+        expected: AssetKind,
+    },
+    LoadFailed {
+        key: String,
+        path: String,
+        reason: String,
+    },
+}
+
+impl fmt::Display for AssetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssetError::DuplicateKey(key) => write!(f, "asset key '{}' is used twice", key),
+            AssetError::NotLoaded(key) => write!(f, "asset '{}' is not loaded", key),
+            AssetError::WrongKind { key, expected } => {
+                write!(f, "asset '{}' is not {:?}", key, expected)
+            }
+            AssetError::LoadFailed { key, path, reason } => {
+                write!(
+                    f,
+                    "failed to load asset '{}' from {}: {}",
+                    key, path, reason
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for AssetError {}
+
+pub enum Asset<I, S> {
+    Image(I),
+    Sound(S),
+}
+
+/// Завантажені файли за ключами. Типи картинки `I` і звуку `S` задає
+/// платформа (у браузері — `HtmlImageElement` і `HtmlAudioElement`).
+pub struct AssetCache<I, S> {
+    assets: HashMap<String, Asset<I, S>>,
+}
+
+impl<I, S> Default for AssetCache<I, S> {
+    fn default() -> Self {
+        Self {
+            assets: HashMap::new(),
+        }
+    }
+}
+
+impl<I, S> AssetCache<I, S> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.assets.contains_key(key)
+    }
+
+    pub fn insert(&mut self, key: &str, asset: Asset<I, S>) {
+        self.assets.insert(key.to_string(), asset);
+    }
+
+    /// Записи маніфесту, яких ще немає в кеші, — що лишилось завантажити.
+    pub fn pending(&self, manifest: &AssetManifest) -> Vec<AssetEntry> {
+        manifest
+            .entries()
+            .iter()
+            .filter(|e| !self.contains(&e.key))
+            .cloned()
+            .collect()
+    }
+
+    pub fn progress(&self, manifest: &AssetManifest) -> LoadProgress {
+        let total = manifest.entries().len();
+        LoadProgress {
+            loaded: total - self.pending(manifest).len(),
+            total,
+        }
+    }
+
+    pub fn image(&self, key: &str) -> Result<&I, AssetError> {
+        match self.assets.get(key) {
+            Some(Asset::Image(image)) => Ok(image),
+            Some(Asset::Sound(_)) => Err(AssetError::WrongKind {
+                key: key.to_string(),
+                expected: AssetKind::Image,
+            }),
+            None => Err(AssetError::NotLoaded(key.to_string())),
+        }
+    }
+
+    pub fn sound(&self, key: &str) -> Result<&S, AssetError> {
+        match self.assets.get(key) {
+            Some(Asset::Sound(sound)) => Ok(sound),
+            Some(Asset::Image(_)) => Err(AssetError::WrongKind {
+                key: key.to_string(),
+                expected: AssetKind::Sound,
+            }),
+            None => Err(AssetError::NotLoaded(key.to_string())),
+        }
+    }
+
+    pub fn images(&self) -> impl Iterator<Item = (&str, &I)> {
+        self.assets.iter().filter_map(|(key, asset)| match asset {
+            Asset::Image(image) => Some((key.as_str(), image)),
+            Asset::Sound(_) => None,
+        })
+    }
+}
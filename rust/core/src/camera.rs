@@ -0,0 +1,159 @@
+//! Камера: яку частину світу видно на полотні і в якому масштабі.
+//!
+//! Екранні координати — пікселі полотна (як `offsetX`/`offsetY` миші),
+//! світові — пікселі карти. Точка світу `p` потрапляє на екран у
+//! `(p - (x, y)) * zoom`.
+
+use crate::models::position::Position;
+
+pub const MIN_ZOOM: f64 = 0.25;
+pub const MAX_ZOOM: f64 = 3.0;
+const FOLLOW_SHARPNESS: f64 = 6.0; // більше — камера швидше наздоганяє ціль
+const EDGE_MARGIN: f64 = 30.0; // px екрана від краю, де починається прокрутка
+const EDGE_PAN_SPEED: f64 = 700.0; // px екрана за секунду
+const MANUAL_HOLD: f64 = 2.0; // с, скільки камера не стежить за ціллю після ручної прокрутки
+
+pub struct Camera {
+    pub x: f64, // лівий верхній кут видимої області у світі
+    pub y: f64,
+    pub zoom: f64,
+    pub viewport_width: f64,
+    pub viewport_height: f64,
+    pub world_width: f64,
+    pub world_height: f64,
+    pointer: Option<Position>, // де миша на екрані, якщо над полотном
+    manual_hold: f64,
+}
+
+impl Camera {
+    pub fn new(
+        viewport_width: f64,
+        viewport_height: f64,
+        world_width: f64,
+        world_height: f64,
+    ) -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            zoom: 1.0,
+            viewport_width,
+            viewport_height,
+            world_width,
+            world_height,
+            pointer: None,
+            manual_hold: 0.0,
+        }
+    }
+
+    pub fn screen_to_world(&self, screen: &Position) -> Position {
+        Position::new(self.x + screen.x / self.zoom, self.y + screen.y / self.zoom)
+    }
+
+    pub fn world_to_screen(&self, world: &Position) -> Position {
+        Position::new(
+            (world.x - self.x) * self.zoom,
+            (world.y - self.y) * self.zoom,
+        )
+    }
+
+    /// Скільки світу вміщається на екрані при поточному масштабі.
+    pub fn visible_width(&self) -> f64 {
+        self.viewport_width / self.zoom
+    }
+
+    pub fn visible_height(&self) -> f64 {
+        self.viewport_height / self.zoom
+    }
+
+    pub fn resize_viewport(&mut self, width: f64, height: f64) {
+        self.viewport_width = width;
+        self.viewport_height = height;
+        self.clamp();
+    }
+
+    /// Масштабує навколо точки екрана так, щоб під курсором лишалась та сама точка світу.
+    pub fn zoom_at(&mut self, screen: &Position, factor: f64) {
+        let anchor = self.screen_to_world(screen);
+        self.zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+        self.x = anchor.x - screen.x / self.zoom;
+        self.y = anchor.y - screen.y / self.zoom;
+        self.clamp();
+    }
+
+    /// Зсув у пікселях екрана; на час `MANUAL_HOLD` вимикає стеження.
+    pub fn pan(&mut self, dx: f64, dy: f64) {
+        self.x += dx / self.zoom;
+        self.y += dy / self.zoom;
+        self.manual_hold = MANUAL_HOLD;
+        self.clamp();
+    }
+
+    pub fn set_pointer(&mut self, pointer: Option<Position>) {
+        self.pointer = pointer;
+    }
+
+    /// Щокадрове оновлення: прокрутка біля країв екрана, інакше — плавне
+    /// стеження за `target` (точкою світу, яку тримаємо в центрі).
+    pub fn update(&mut self, delta_time: f64, target: Option<Position>) {
+        let (dx, dy) = self.edge_direction();
+        if dx != 0.0 || dy != 0.0 {
+            let step = EDGE_PAN_SPEED * delta_time;
+            self.pan(dx * step, dy * step);
+            return;
+        }
+
+        if self.manual_hold > 0.0 {
+            self.manual_hold -= delta_time;
+            return;
+        }
+
+        if let Some(target) = target {
+            let goal_x = target.x - self.visible_width() / 2.0;
+            let goal_y = target.y - self.visible_height() / 2.0;
+            // експоненційне згладжування, що не залежить від FPS
+            let t = 1.0 - (-FOLLOW_SHARPNESS * delta_time).exp();
+            self.x += (goal_x - self.x) * t;
+            self.y += (goal_y - self.y) * t;
+            self.clamp();
+        }
+    }
+
+    /// Миттєво ставить ціль у центр (напр. на старті матчу).
+    pub fn center_on(&mut self, target: &Position) {
+        self.x = target.x - self.visible_width() / 2.0;
+        self.y = target.y - self.visible_height() / 2.0;
+        self.clamp();
+    }
+
+    fn edge_direction(&self) -> (f64, f64) {
+        let Some(pointer) = self.pointer else {
+            return (0.0, 0.0);
+        };
+        let axis = |value: f64, size: f64| {
+            if value < EDGE_MARGIN {
+                -1.0
+            } else if value > size - EDGE_MARGIN {
+                1.0
+            } else {
+                0.0
+            }
+        };
+        (
+            axis(pointer.x, self.viewport_width),
+            axis(pointer.y, self.viewport_height),
+        )
+    }
+
+    /// Не даємо заїхати за край світу; якщо світ менший за екран — центруємо.
+    fn clamp(&mut self) {
+        let fit = |offset: f64, visible: f64, world: f64| {
+            if visible >= world {
+                (world - visible) / 2.0
+            } else {
+                offset.clamp(0.0, world - visible)
+            }
+        };
+        self.x = fit(self.x, self.visible_width(), self.world_width);
+        self.y = fit(self.y, self.visible_height(), self.world_height);
+    }
+}
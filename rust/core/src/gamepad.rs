@@ -0,0 +1,104 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::input::Action;
+
+pub const DEFAULT_DEAD_ZONE: f64 = 0.25;
+
+// Індекси кнопок і осей у "standard" розкладці Gamepad API
+const BUTTON_A: usize = 0;
+const BUTTON_X: usize = 2;
+const BUTTON_Y: usize = 3;
+const BUTTON_RB: usize = 5;
+const BUTTON_RT: usize = 7;
+const DPAD_UP: usize = 12;
+const DPAD_DOWN: usize = 13;
+const DPAD_LEFT: usize = 14;
+const DPAD_RIGHT: usize = 15;
+const AXIS_LEFT_X: usize = 0;
+const AXIS_LEFT_Y: usize = 1;
+
+/// Перекладає стан геймпада (осі -1.0..1.0, кнопки) у ті самі дії, що й клавіатура.
+pub struct GamepadMapping {
+    pub dead_zone: f64,
+}
+
+impl GamepadMapping {
+    pub fn actions(&self, axes: &[f64], buttons: &[bool]) -> HashSet<Action> {
+        let axis = |i: usize| {
+            let value = axes.get(i).copied().unwrap_or(0.0);
+            if value.abs() < self.dead_zone {
+                0.0
+            } else {
+                value
+            }
+        };
+        let button = |i: usize| buttons.get(i).copied().unwrap_or(false);
+
+        let mut actions = HashSet::new();
+        if axis(AXIS_LEFT_X) < 0.0 || button(DPAD_LEFT) {
+            actions.insert(Action::MoveLeft);
+        }
+        if axis(AXIS_LEFT_X) > 0.0 || button(DPAD_RIGHT) {
+            actions.insert(Action::MoveRight);
+        }
+        // вісь Y у Gamepad API додатна донизу
+        if axis(AXIS_LEFT_Y) < 0.0 || button(DPAD_UP) {
+            actions.insert(Action::AimUp);
+        }
+        if axis(AXIS_LEFT_Y) > 0.0 || button(DPAD_DOWN) {
+            actions.insert(Action::AimDown);
+        }
+        if button(BUTTON_A) {
+            actions.insert(Action::Jump);
+        }
+        if button(BUTTON_X) || button(BUTTON_RT) || button(BUTTON_RB) {
+            actions.insert(Action::Fire);
+        }
+        if button(BUTTON_Y) {
+            actions.insert(Action::SwitchWeapon);
+        }
+        actions
+    }
+}
+
+impl Default for GamepadMapping {
+    fn default() -> Self {
+        Self {
+            dead_zone: DEFAULT_DEAD_ZONE,
+        }
+    }
+}
+
+/// Який геймпад грає за яку команду. Новий геймпад займає найменший вільний
+/// індекс команди, відключений — звільняє його.
+#[derive(Default)]
+pub struct GamepadAssignments {
+    teams: BTreeMap<u32, usize>, // індекс геймпада -> команда
+}
+
+impl GamepadAssignments {
+    /// Звіряє призначення зі списком підключених зараз геймпадів.
+    pub fn sync(&mut self, connected: &[u32]) {
+        self.teams.retain(|pad, _| connected.contains(pad));
+        for &pad in connected {
+            if self.teams.contains_key(&pad) {
+                continue;
+            }
+            let team = (0..)
+                .find(|t| !self.teams.values().any(|taken| taken == t))
+                .unwrap_or_default();
+            self.teams.insert(pad, team);
+        }
+    }
+
+    pub fn team_of(&self, pad: u32) -> Option<usize> {
+        self.teams.get(&pad).copied()
+    }
+
+    pub fn pads_for_team(&self, team: usize) -> impl Iterator<Item = u32> + '_ {
+        self.teams
+            .iter()
+            .filter(move |(_, t)| **t == team)
+            .map(|(pad, _)| *pad)
+    }
+}
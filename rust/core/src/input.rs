@@ -0,0 +1,150 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    Jump,
+    AimUp,
+    AimDown,
+    Fire,
+    SwitchWeapon,
+}
+
+impl Action {
+    pub const ALL: [Action; 7] = [
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Jump,
+        Action::AimUp,
+        Action::AimDown,
+        Action::Fire,
+        Action::SwitchWeapon,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::MoveLeft => "move_left",
+            Action::MoveRight => "move_right",
+            Action::Jump => "jump",
+            Action::AimUp => "aim_up",
+            Action::AimDown => "aim_down",
+            Action::Fire => "fire",
+            Action::SwitchWeapon => "switch_weapon",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Action::ALL.iter().copied().find(|a| a.name() == name)
+    }
+}
+
+/// Відповідність "код клавіші (`KeyboardEvent.code`) -> дія". Одній дії можна
+/// призначити кілька клавіш, але кожна клавіша робить лише одну дію.
+pub struct KeyBindings {
+    bindings: HashMap<String, Action>,
+}
+
+impl KeyBindings {
+    pub fn empty() -> Self {
+        Self {
+            bindings: HashMap::new(),
+        }
+    }
+
+    pub fn bind(&mut self, key: &str, action: Action) {
+        self.bindings.insert(key.to_string(), action);
+    }
+
+    pub fn unbind(&mut self, key: &str) {
+        self.bindings.remove(key);
+    }
+
+    pub fn action_for(&self, key: &str) -> Option<Action> {
+        self.bindings.get(key).copied()
+    }
+
+    pub fn keys_for(&self, action: Action) -> Vec<String> {
+        let mut keys: Vec<String> = self
+            .bindings
+            .iter()
+            .filter(|(_, a)| **a == action)
+            .map(|(k, _)| k.clone())
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    /// Перекладає набір натиснутих клавіш у набір дій.
+    pub fn actions<'a>(&self, keys: impl IntoIterator<Item = &'a str>) -> HashSet<Action> {
+        keys.into_iter()
+            .filter_map(|k| self.action_for(k))
+            .collect()
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let mut bindings = Self::empty();
+        for (key, action) in [
+            ("ArrowLeft", Action::MoveLeft),
+            ("KeyA", Action::MoveLeft),
+            ("ArrowRight", Action::MoveRight),
+            ("KeyD", Action::MoveRight),
+            ("Space", Action::Jump),
+            ("KeyW", Action::Jump),
+            ("ArrowUp", Action::AimUp),
+            ("ArrowDown", Action::AimDown),
+            ("KeyS", Action::AimDown),
+            ("KeyF", Action::Fire),
+            ("Enter", Action::Fire),
+            ("KeyQ", Action::SwitchWeapon),
+        ] {
+            bindings.bind(key, action);
+        }
+        bindings
+    }
+}
+
+/// Стан дій одного гравця. Утримані дії — як є зараз; натискання (фронти)
+/// накопичуються, поки крок симуляції їх не спожиє, тож швидкий тап не
+/// губиться навіть у кадрі, де не було жодного кроку.
+#[derive(Default)]
+pub struct InputState {
+    held: HashSet<Action>,
+    pressed: HashSet<Action>,
+}
+
+impl InputState {
+    pub fn set_held(&mut self, actions: HashSet<Action>) {
+        for action in actions.difference(&self.held) {
+            self.pressed.insert(*action);
+        }
+        self.held = actions;
+    }
+
+    pub fn is_held(&self, action: Action) -> bool {
+        self.held.contains(&action)
+    }
+
+    pub fn was_pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn held(&self) -> impl Iterator<Item = &Action> {
+        self.held.iter()
+    }
+
+    /// Кінець кроку симуляції: фронти вже оброблені.
+    pub fn end_step(&mut self) {
+        self.pressed.clear();
+    }
+
+    pub fn clear(&mut self) {
+        self.held.clear();
+        self.pressed.clear();
+    }
+}
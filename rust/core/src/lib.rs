@@ -0,0 +1,13 @@
+//! Симуляція гри без залежності від браузера: карта, фізика, ходи, зброя і
+//! мережевий протокол. Малювання йде через трейт [`render::Renderer`], тож ядро
+//! можна ганяти в нативних тестах і на сервері.
+
+pub mod animation;
+pub mod assets;
+pub mod camera;
+pub mod gamepad;
+pub mod input;
+pub mod models;
+pub mod protocol;
+pub mod render;
+pub mod tileset;
@@ -0,0 +1,316 @@
+//! Фізичне тіло-прямокутник: гравітація, зіткнення з тайлами, ходьба по
+//! уступах і тертя. Сутність, що тримає `PhysicsBody` і реалізує `HasBody`,
+//! отримує `GravityObject` і `MovableObject` без власного коду фізики.
+
+use crate::models::map::Map;
+use crate::models::material::Material;
+use crate::models::position::Position;
+
+// Фізика в пікселях і секундах, щоб не залежати від частоти кадрів
+pub const GRAVITY: f64 = 1800.0; // px/s²
+pub const WALK_SPEED: f64 = 300.0; // px/s
+pub const WALK_ACCELERATION: f64 = 2400.0; // px/s², на землі з нормальним зчепленням
+pub const AIR_ACCELERATION: f64 = 600.0; // px/s², підрулювання в польоті
+const GROUND_FRICTION_RATE: f64 = 60.0; // 1/с на одиницю тертя матеріалу: ґрунт гальмує за ~0.1 с
+const AIR_DRAG: f64 = 1.0; // 1/с, опір повітря для горизонтальної швидкості
+const MIN_BOUNCE_SPEED: f64 = 150.0; // px/s, повільніше на пружній поверхні не підскакуємо
+const SOFT_LANDING_SPEED: f64 = 200.0; // px/s, повільніший дотик — не приземлення, а крок
+const MAX_STEP: f64 = 1.0; // субкрок — не більше 1px за раз, щоб не проскочити тайл
+
+pub struct PhysicsBody {
+    pub position: Position,          // лівий верхній кут
+    pub previous_position: Position, // позиція на початку останнього кроку — для інтерполяції
+    pub velocity_x: f64,             // px/s
+    pub velocity_y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub step_height: f64, // px, найвищий уступ, на який заходимо і з якого сходимо пішки
+    airborne: bool,       // з останнього дотику до землі вже рухались по вертикалі
+    impact: Option<f64>,  // швидкість останнього приземлення, ще не забрана
+}
+
+impl PhysicsBody {
+    pub fn new(position: Position, width: f64, height: f64) -> Self {
+        Self {
+            position,
+            previous_position: position,
+            velocity_x: 0.0,
+            velocity_y: 0.0,
+            width,
+            height,
+            step_height: 0.0,
+            airborne: false,
+            impact: None,
+        }
+    }
+
+    pub fn center(&self) -> Position {
+        Position::new(
+            self.position.x + self.width / 2.0,
+            self.position.y + self.height / 2.0,
+        )
+    }
+
+    /// Ставить тіло в точку без швидкості й без інтерполяції з попередньої.
+    pub fn teleport(&mut self, position: Position) {
+        self.position = position;
+        self.previous_position = position;
+        self.velocity_x = 0.0;
+        self.velocity_y = 0.0;
+    }
+
+    /// Поштовх (px/s), напр. від вибуху.
+    pub fn apply_impulse(&mut self, dx: f64, dy: f64) {
+        self.velocity_x += dx;
+        self.velocity_y += dy;
+    }
+
+    /// Стоїть на тайлі або на нижньому краю світу.
+    pub fn is_grounded(&self, map: &Map, world_height: f64) -> bool {
+        self.is_on_ground(map) || self.position.y + self.height >= world_height
+    }
+
+    pub fn is_on_ground(&self, map: &Map) -> bool {
+        let feet_y = self.position.y + self.height + 1.0;
+        let check_points = [
+            self.position.x,
+            self.position.x + self.width / 2.0,
+            self.position.x + self.width - 1.0,
+        ];
+        check_points.iter().any(|&x| map.is_solid_at(x, feet_y))
+    }
+
+    /// Матеріал під центром ніг.
+    pub fn ground_material(&self, map: &Map) -> Material {
+        map.material_at(
+            self.position.x + self.width / 2.0,
+            self.position.y + self.height + 1.0,
+        )
+    }
+
+    /// Матеріал під центром тіла — напр. рідина, в яку воно занурилось.
+    /// Повітря під лінією води карти (`Map::water_level`) теж вода.
+    pub fn submerged_in(&self, map: &Map) -> Material {
+        let center = self.center();
+        let under_water = map.water_level.is_some_and(|water| center.y > water);
+        match map.material_at(center.x, center.y) {
+            Material::Air if under_water => Material::Water,
+            material => material,
+        }
+    }
+
+    /// Швидкість останнього приземлення після польоту, якщо його ще не забрали.
+    pub fn take_impact(&mut self) -> Option<f64> {
+        self.impact.take()
+    }
+
+    /// Повний крок фізики тіла: горизонталь (розгін у бік `direction` або
+    /// тертя), гравітація з рухом до зіткнення. Повертає швидкість
+    /// приземлення, якщо тіло на цьому кроці приземлилось після польоту.
+    pub fn step(
+        &mut self,
+        delta_time: f64,
+        map: &Map,
+        world_height: f64,
+        direction: f64,
+    ) -> Option<f64> {
+        self.previous_position = self.position;
+        let is_on_ground = self.is_grounded(map, world_height);
+        self.apply_horizontal(delta_time, map, direction, is_on_ground);
+        self.apply_gravity(delta_time, map, world_height);
+        self.take_impact()
+    }
+
+    /// Горизонтальна швидкість: розгін у бік `direction` (-1, 0 або 1) не
+    /// швидше за `WALK_SPEED`, тертя об ґрунт під ногами або опір повітря.
+    /// Швидкість від поштовхів понад `WALK_SPEED` ходьба не обрізає.
+    pub fn apply_horizontal(
+        &mut self,
+        delta_time: f64,
+        map: &Map,
+        direction: f64,
+        is_on_ground: bool,
+    ) {
+        // зчеплення відносно звичайного ґрунту: на льоду розганяємось і гальмуємо повільно
+        let grip = self.ground_material(map).friction() / Material::Dirt.friction();
+        let acceleration = if is_on_ground {
+            WALK_ACCELERATION * grip.min(1.0)
+        } else {
+            AIR_ACCELERATION
+        };
+
+        let target = direction * WALK_SPEED;
+        if direction != 0.0 && self.velocity_x * direction < WALK_SPEED {
+            let dv = (target - self.velocity_x)
+                .abs()
+                .min(acceleration * delta_time);
+            self.velocity_x += dv * direction;
+        } else {
+            let rate = if is_on_ground && self.velocity_y >= 0.0 {
+                GROUND_FRICTION_RATE * self.ground_material(map).friction()
+            } else {
+                AIR_DRAG
+            };
+            self.velocity_x *= 1.0 - (rate * delta_time).min(1.0);
+            if direction != 0.0 && self.velocity_x * direction < WALK_SPEED {
+                self.velocity_x = target; // поштовх згас до кроку — далі просто йдемо
+            } else if self.velocity_x.abs() < 1.0 {
+                self.velocity_x = 0.0;
+            }
+        }
+
+        // стрибок чи відкидання вгору відриває від землі — тоді без уступів
+        let walking = is_on_ground && self.velocity_y >= 0.0;
+        let mut remaining = self.velocity_x * delta_time;
+        let step = MAX_STEP.copysign(self.velocity_x);
+        while remaining.abs() > 0.0 {
+            let dx = if remaining.abs() >= MAX_STEP {
+                step
+            } else {
+                remaining
+            };
+            let moved = self.try_move_x(dx, map) || (walking && self.try_step_up(dx, map));
+            if !moved {
+                self.velocity_x = 0.0;
+                return;
+            }
+            if walking {
+                self.stick_to_ground(map);
+            }
+            remaining -= dx;
+        }
+    }
+
+    /// Заходить на уступ не вищий за `step_height`, якщо над ним вистачає місця.
+    fn try_step_up(&mut self, dx: f64, map: &Map) -> bool {
+        let new_x = self.position.x + dx;
+        let mut rise = 1.0;
+        while rise <= self.step_height {
+            let new_y = self.position.y - rise;
+            if map.can_move_to(new_x, new_y, self.width, self.height) {
+                self.position = Position::new(new_x, new_y);
+                return true;
+            }
+            rise += 1.0;
+        }
+        false
+    }
+
+    /// Спускаючись схилом, не відриваємось від землі: якщо під ногами з'явилась
+    /// яма не глибша за `step_height`, одразу стаємо на її дно.
+    fn stick_to_ground(&mut self, map: &Map) {
+        if self.is_on_ground(map) {
+            return;
+        }
+        let start_y = self.position.y;
+        let mut drop = 1.0;
+        while drop <= self.step_height {
+            self.position.y = start_y + drop;
+            if self.is_on_ground(map) {
+                let feet_y = self.position.y + self.height + 1.0;
+                self.position.y = (feet_y / map.tile_size).floor() * map.tile_size - self.height;
+                return;
+            }
+            drop += 1.0;
+        }
+        self.position.y = start_y; // обрив — падаємо як звичайно
+    }
+
+    /// Зсув по горизонталі, якщо там вільно. Уперлися — стаємо впритул до тайла.
+    pub fn try_move_x(&mut self, dx: f64, map: &Map) -> bool {
+        let new_x = self.position.x + dx;
+        if map.can_move_to(new_x, self.position.y, self.width, self.height) {
+            self.position.x = new_x;
+            return true;
+        }
+
+        let flush_x = if dx > 0.0 {
+            let col = ((new_x + self.width - 1.0) / map.tile_size).floor();
+            col * map.tile_size - self.width
+        } else {
+            let col = (new_x / map.tile_size).floor();
+            (col + 1.0) * map.tile_size
+        };
+        if map.can_move_to(flush_x, self.position.y, self.width, self.height) {
+            self.position.x = flush_x;
+        }
+        false
+    }
+
+    /// Гравітація і вертикальний рух субкроками до першого зіткнення.
+    pub fn apply_gravity(&mut self, delta_time: f64, map: &Map, world_height: f64) {
+        self.velocity_y += GRAVITY * delta_time;
+
+        let mut remaining = self.velocity_y * delta_time;
+        let step = MAX_STEP.copysign(self.velocity_y); // +1 або -1
+
+        while remaining.abs() >= MAX_STEP {
+            if !self.try_move_y(step, map, world_height) {
+                self.stop_vertical(map);
+                return;
+            }
+            remaining -= step;
+        }
+
+        // останній малий крок
+        if remaining.abs() > 0.0 && !self.try_move_y(remaining, map, world_height) {
+            self.stop_vertical(map);
+        }
+    }
+
+    /// Уперлися в тайл: зупиняємось або, на пружній поверхні, підскакуємо.
+    fn stop_vertical(&mut self, map: &Map) {
+        let bounce = self.velocity_y * self.ground_material(map).restitution();
+        self.velocity_y = if self.velocity_y > 0.0 && bounce > MIN_BOUNCE_SPEED {
+            -bounce
+        } else {
+            0.0
+        };
+    }
+
+    pub fn try_move_y(&mut self, dy: f64, map: &Map, world_height: f64) -> bool {
+        self.position.y += dy;
+
+        if dy > 0.0 && self.is_on_ground(map) {
+            let feet_y = self.position.y + self.height + 1.0;
+            let tile_row = (feet_y / map.tile_size).floor();
+            self.position.y = tile_row * map.tile_size - self.height;
+            self.touch_down();
+            return false;
+        }
+
+        if dy < 0.0 {
+            let head_y = self.position.y;
+            let check_points = [
+                self.position.x + 1.0,
+                self.position.x + self.width / 2.0,
+                self.position.x + self.width - 1.0,
+            ];
+            for &px in &check_points {
+                if map.is_solid_at(px, head_y) {
+                    let tile_row = (head_y / map.tile_size).floor();
+                    self.position.y = (tile_row + 1.0) * map.tile_size;
+                    return false;
+                }
+            }
+        }
+
+        if self.position.y + self.height >= world_height {
+            self.position.y = world_height - self.height;
+            if dy > 0.0 {
+                self.touch_down();
+            }
+            return false;
+        }
+
+        self.airborne = true;
+        true
+    }
+
+    /// Торкнулись землі. Якщо перед цим летіли досить швидко — запам'ятовуємо удар.
+    fn touch_down(&mut self) {
+        if std::mem::take(&mut self.airborne) && self.velocity_y >= SOFT_LANDING_SPEED {
+            self.impact = Some(self.velocity_y);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::models::projectile::WeaponKind;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GameEvent {
+    Explosion {
+        x: f64,
+        y: f64,
+        radius: f64,
+        tiles_removed: usize,
+    },
+    /// Гравець приземлився після польоту; `damage` — скільки забрало падіння.
+    PlayerLanded {
+        player: usize,
+        impact_speed: f64,
+        damage: f64,
+    },
+    PlayerDied {
+        player: usize,
+    },
+    /// Снаряд вилетів; `angle` і `power` — як у `Game::fire`. Власник ходу
+    /// пересилає це іншим клієнтам, щоб вони вистрелили так само.
+    ShotFired {
+        weapon: WeaponKind,
+        angle: f64,
+        power: f64,
+    },
+    /// Подія кадру анімації гравця (`footstep`, `land`, `death`) — для звуків і ефектів.
+    PlayerAnimation {
+        player: usize,
+        event: String,
+    },
+    TurnChanged {
+        team: usize,
+        player: usize,
+    },
+}
@@ -0,0 +1,528 @@
+use std::f64::consts::PI;
+
+use crate::camera::Camera;
+use crate::input::Action;
+use crate::models::body::PhysicsBody;
+use crate::models::event::GameEvent;
+use crate::models::map::Map;
+use crate::models::player::Player;
+use crate::models::position::Position;
+use crate::models::projectile::{Detonation, DetonationCallback, Projectile, WeaponKind};
+use crate::models::spawn;
+use crate::models::traits::{CanvasObject, Entity};
+use crate::models::turn::{TeamError, TurnManager};
+use crate::protocol::{FireInput, PlayerState};
+use crate::render::Renderer;
+use crate::tileset::TileSet;
+
+/// Крок симуляції фіксований, щоб фізика не залежала від FPS і таймерів браузера.
+pub const FIXED_TIMESTEP: f64 = 1.0 / 60.0;
+/// Якщо вкладка "заснула", не намагаємось наздогнати більше ніж стільки секунд.
+const MAX_FRAME_TIME: f64 = 0.25;
+/// Сила пострілу з клавіатури (з JS можна передати свою через `fire`).
+pub const DEFAULT_FIRE_POWER: f64 = 0.75;
+const CROSSHAIR_DISTANCE: f64 = 50.0;
+/// Шкода в епіцентрі; до краю зони ураження спадає до нуля.
+pub const EXPLOSION_DAMAGE: f64 = 50.0;
+/// Швидкість відкидання в епіцентрі, px/s.
+pub const EXPLOSION_KNOCKBACK: f64 = 700.0;
+
+pub struct Game {
+    pub map: Map,
+    pub players: Vec<Player>,
+    pub objects: Vec<Box<dyn Entity>>,
+    pub canvas_width: f64,
+    pub canvas_height: f64,
+    pub camera: Camera,
+    pub events: Vec<GameEvent>,
+    pub projectiles: Vec<Projectile>,
+    pub wind: f64,
+    pub turns: TurnManager,
+    pub tileset: Option<TileSet>,
+    /// Хід веде інший клієнт: таймер іде, але хід передаємо лише за його повідомленням.
+    pub follow_remote_turns: bool,
+    accumulator: f64,
+    on_detonation: Option<DetonationCallback>,
+}
+
+impl Game {
+    /// Світ завбільшки з полотно.
+    pub fn new(canvas_width: f64, canvas_height: f64) -> Self {
+        Self::with_world(canvas_width, canvas_height, canvas_width, canvas_height)
+    }
+
+    /// Світ `world_width` x `world_height`, який камера показує на полотні
+    /// `canvas_width` x `canvas_height`.
+    pub fn with_world(
+        world_width: f64,
+        world_height: f64,
+        canvas_width: f64,
+        canvas_height: f64,
+    ) -> Self {
+        let map = Map::new(world_width, world_height);
+        let camera = Camera::new(canvas_width, canvas_height, map.width(), map.height());
+        let players = Vec::new();
+        let objects: Vec<Box<dyn Entity>> = Vec::new();
+
+        Game {
+            map,
+            players,
+            objects,
+            canvas_width,
+            canvas_height,
+            camera,
+            events: Vec::new(),
+            projectiles: Vec::new(),
+            wind: 0.0,
+            turns: TurnManager::default(),
+            tileset: None,
+            follow_remote_turns: false,
+            accumulator: 0.0,
+            on_detonation: None,
+        }
+    }
+
+    pub fn add_team(&mut self, name: &str) -> usize {
+        self.turns.add_team(name)
+    }
+
+    /// Команда з вибраними назвою й кольором. Колір не має повторюватись у матчі.
+    pub fn create_team(&mut self, name: &str, color: &str) -> Result<usize, TeamError> {
+        self.turns.add_team_with_color(name, color)
+    }
+
+    /// Назва й колір команди, про які домовились через сервер. Команда, що
+    /// вже мала цей колір, міняється з `team` кольорами.
+    pub fn assign_team(&mut self, team: usize, name: &str, color: &str) -> Result<(), TeamError> {
+        let swapped = self.turns.set_team_color(team, color)?;
+        self.turns.teams[team].name = name.to_string();
+        for t in std::iter::once(team).chain(swapped) {
+            self.repaint_team(t);
+        }
+        Ok(())
+    }
+
+    /// Черв'як малюється спрайтшитом кольору своєї команди.
+    pub fn add_player_to_team(&mut self, team: usize, player: Player) {
+        self.players.push(player);
+        self.turns.add_member(team, self.players.len() - 1);
+        self.repaint_team(team);
+    }
+
+    fn repaint_team(&mut self, team: usize) {
+        let Some(team) = self.turns.teams.get(team) else {
+            return;
+        };
+        let sprite = team.sprite();
+        for &member in &team.members {
+            if let Some(animation) = self
+                .players
+                .get_mut(member)
+                .and_then(|p| p.animation.as_mut())
+            {
+                animation.set_sprite(&sprite);
+            }
+        }
+    }
+
+    /// Гравець без явної команди отримує власну команду.
+    pub fn add_player(&mut self, player: Player) {
+        let team = self.add_team(&format!("Team {}", self.turns.teams.len() + 1));
+        self.add_player_to_team(team, player);
+    }
+
+    /// Ставить гравців на точки появи карти: спершу на точки своєї команди,
+    /// потім на спільні. Кому точки не вистачило, шукаємо безпечне місце на
+    /// рельєфі (див. `spawn`). Кожен поставлений гравець грає анімацію появи.
+    pub fn place_players_at_spawns(&mut self) {
+        let mut used = vec![false; self.map.spawn_points.len()];
+        let mut taken = Vec::new();
+        let mut unplaced = Vec::new();
+        for (team_index, team) in self.turns.teams.iter().enumerate() {
+            for &member in &team.members {
+                let spawn = self
+                    .map
+                    .spawn_points
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, s)| !used[*i] && s.team.is_none_or(|t| t == team_index))
+                    .min_by_key(|(_, s)| s.team.is_none());
+                let Some(player) = self.players.get_mut(member) else {
+                    continue;
+                };
+                let Some((i, spawn)) = spawn else {
+                    unplaced.push((team_index, member));
+                    continue;
+                };
+                used[i] = true;
+                let body = &mut player.body;
+                body.teleport(Position::new(
+                    spawn.x - body.width / 2.0,
+                    spawn.y - body.height,
+                ));
+                taken.push(body.position);
+                player.start_spawn();
+            }
+        }
+
+        let team_count = self.turns.teams.len();
+        for (team, member) in unplaced {
+            let player = &mut self.players[member];
+            let (width, height) = (player.body.width, player.body.height);
+            let spots = spawn::standing_spots(&self.map, width, height);
+            let Some(spot) = spawn::choose_spot(&self.map, &spots, &taken, team, team_count, width)
+            else {
+                continue; // на карті немає де стати — лишаємо де був
+            };
+            player.body.teleport(spot);
+            taken.push(spot);
+            player.start_spawn();
+        }
+    }
+
+    pub fn add_object(&mut self, object: Box<dyn Entity>) {
+        self.objects.push(object);
+    }
+
+    /// Запускає снаряд з центру поточного гравця в бік, куди він дивиться.
+    /// `angle` у радіанах від горизонталі, `power` від 0.0 до 1.0.
+    /// Повертає `false`, якщо стріляти зараз не можна і снаряд не вилетів.
+    pub fn fire(&mut self, weapon: WeaponKind, angle: f64, power: f64) -> bool {
+        if !self.turns.can_fire() {
+            return false;
+        }
+        let Some(player) = self
+            .turns
+            .current_player()
+            .and_then(|i| self.players.get(i))
+            .filter(|p| !p.is_spawning())
+        else {
+            return false;
+        };
+        let center = player.body.center();
+        self.projectiles.push(Projectile::launch(
+            weapon,
+            center.x,
+            center.y,
+            world_angle(player, angle),
+            power,
+        ));
+        self.turns.on_fired();
+        self.events.push(GameEvent::ShotFired {
+            weapon,
+            angle,
+            power,
+        });
+        true
+    }
+
+    pub fn set_detonation_callback(&mut self, callback: impl FnMut(&Detonation) + 'static) {
+        self.on_detonation = Some(Box::new(callback));
+    }
+
+    pub fn explode(&mut self, x: f64, y: f64, radius: f64) {
+        let tiles_removed = self.map.carve_circle(x, y, radius);
+        self.events.push(GameEvent::Explosion {
+            x,
+            y,
+            radius,
+            tiles_removed,
+        });
+
+        for player in self.players.iter_mut().filter(|p| p.is_alive()) {
+            let Some(falloff) = blast(&mut player.body, x, y, radius) else {
+                continue;
+            };
+            player.take_damage(EXPLOSION_DAMAGE * falloff);
+        }
+        for object in self.objects.iter_mut() {
+            blast(object.body_mut(), x, y, radius);
+        }
+    }
+
+    /// Загиблі виходять з черги ходів; якщо загинув той, чий хід, — хід переходить далі.
+    fn remove_dead_players(&mut self) {
+        for index in 0..self.players.len() {
+            if self.players[index].is_alive() || !self.turns.remove_member(index) {
+                continue;
+            }
+            self.events.push(GameEvent::PlayerDied { player: index });
+            if self.turns.current_player() == Some(index) && !self.follow_remote_turns {
+                self.end_turn();
+            }
+        }
+    }
+
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    pub fn draw(&mut self, renderer: &dyn Renderer) {
+        renderer.clear(self.canvas_width, self.canvas_height);
+
+        renderer.push_transform(self.camera.x, self.camera.y, self.camera.zoom);
+
+        let dirty = self.map.take_dirty();
+        self.map.draw_cached(renderer, self.tileset.as_ref(), dirty);
+
+        let alpha = self.interpolation_alpha();
+        // спершу всі тіні, щоб жодна не лягла поверх сусіднього черв'яка
+        self.players.iter().for_each(|player| {
+            player.draw_shadow(renderer, &self.map, alpha);
+        });
+        self.players.iter().for_each(|player| {
+            player.draw_interpolated(renderer, alpha);
+        });
+
+        if let Some(player) = self
+            .turns
+            .current_player()
+            .and_then(|i| self.players.get(i))
+        {
+            if self.turns.can_fire() {
+                let (x, y) = aim_point(player, CROSSHAIR_DISTANCE);
+                renderer.fill_circle(x, y, 3.0, "red");
+            }
+        }
+
+        self.objects.iter().for_each(|object| {
+            object.draw(renderer);
+        });
+
+        self.projectiles.iter().for_each(|projectile| {
+            projectile.draw(renderer, alpha);
+        });
+
+        renderer.pop_transform();
+    }
+
+    /// Змінився розмір полотна: світ, гравці й снаряди лишаються як були,
+    /// змінюється лише те, скільки світу видно.
+    pub fn resize_viewport(&mut self, canvas_width: f64, canvas_height: f64) {
+        self.canvas_width = canvas_width;
+        self.canvas_height = canvas_height;
+        self.camera.resize_viewport(canvas_width, canvas_height);
+    }
+
+    /// Нова карта (інший розмір світу — теж): камера підлаштовується під її межі.
+    pub fn set_map(&mut self, map: Map) {
+        self.camera.world_width = map.width();
+        self.camera.world_height = map.height();
+        self.map = map;
+        self.camera
+            .resize_viewport(self.canvas_width, self.canvas_height);
+    }
+
+    /// За чим стежить камера: снаряд у польоті, інакше — гравець, чий хід.
+    pub fn camera_target(&self) -> Option<Position> {
+        if let Some(projectile) = self.projectiles.first() {
+            return Some(projectile.position);
+        }
+        let player = self.players.get(self.turns.current_player()?)?;
+        Some(player.body.center())
+    }
+
+    pub fn get_current_player_mut(&mut self) -> Option<&mut Player> {
+        let index = self.turns.current_player()?;
+        self.players.get_mut(index)
+    }
+
+    pub fn end_turn(&mut self) {
+        if let Some(player) = self.get_current_player_mut() {
+            player.input.clear();
+        }
+
+        self.turns.next_turn();
+        if let (Some(team), Some(player)) = (self.turns.current_team(), self.turns.current_player())
+        {
+            self.events.push(GameEvent::TurnChanged { team, player });
+        }
+    }
+
+    pub fn force_turn(&mut self, team: usize, player: usize) {
+        if let Some(current) = self.get_current_player_mut() {
+            current.input.clear();
+        }
+        self.turns.force_turn(team, player);
+    }
+
+    pub fn snapshot(&self) -> Vec<PlayerState> {
+        self.players
+            .iter()
+            .map(|p| PlayerState {
+                x: p.body.position.x,
+                y: p.body.position.y,
+                velocity_x: p.body.velocity_x,
+                velocity_y: p.body.velocity_y,
+                facing_left: p.facing_left,
+                health: p.health,
+            })
+            .collect()
+    }
+
+    pub fn apply_snapshot(&mut self, states: &[PlayerState]) {
+        for (player, state) in self.players.iter_mut().zip(states) {
+            player.body.teleport(Position::new(state.x, state.y));
+            player.body.velocity_x = state.velocity_x;
+            player.body.velocity_y = state.velocity_y;
+            player.facing_left = state.facing_left;
+            player.sync_health(state.health);
+        }
+        self.remove_dead_players();
+    }
+
+    /// Ввід від віддаленого клієнта, чий зараз хід. Стріляємо лише за `fire`:
+    /// клавіша пострілу без кута й сили власника розсинхронізувала б снаряди.
+    pub fn apply_remote_input(&mut self, actions: Vec<Action>, fire: Option<FireInput>) {
+        if let Some(player) = self.get_current_player_mut() {
+            let actions = actions.into_iter().filter(|a| *a != Action::Fire);
+            player.set_actions(actions.collect());
+        }
+        if let Some(fire) = fire {
+            if let Some(weapon) = WeaponKind::from_name(&fire.weapon) {
+                self.fire(weapon, fire.angle, fire.power);
+            }
+        }
+    }
+
+    /// Годує акумулятор реальним часом кадру і проганяє стільки фіксованих
+    /// кроків, скільки накопичилось. Повертає кількість виконаних кроків.
+    pub fn update(&mut self, elapsed: f64) -> usize {
+        self.accumulator += elapsed.clamp(0.0, MAX_FRAME_TIME);
+
+        let mut steps = 0;
+        while self.accumulator >= FIXED_TIMESTEP {
+            self.step();
+            self.accumulator -= FIXED_TIMESTEP;
+            steps += 1;
+        }
+
+        let target = self.camera_target();
+        self.camera
+            .update(elapsed.clamp(0.0, MAX_FRAME_TIME), target);
+        steps
+    }
+
+    /// Частка незавершеного кроку — для інтерполяції під час малювання.
+    pub fn interpolation_alpha(&self) -> f64 {
+        self.accumulator / FIXED_TIMESTEP
+    }
+
+    pub fn step(&mut self) {
+        let delta_time = FIXED_TIMESTEP;
+        let map = &self.map;
+        let world_height = map.height();
+
+        self.players.iter_mut().for_each(|player| {
+            player.update(delta_time, map, world_height);
+        });
+        for (index, player) in self.players.iter_mut().enumerate() {
+            let Some(landing) = player.take_landing() else {
+                continue;
+            };
+            self.events.push(GameEvent::PlayerLanded {
+                player: index,
+                impact_speed: landing.impact_speed,
+                damage: landing.damage,
+            });
+        }
+
+        for (index, player) in self.players.iter().enumerate() {
+            let Some(animation) = &player.animation else {
+                continue;
+            };
+            self.events.extend(
+                animation
+                    .events()
+                    .iter()
+                    .map(|event| GameEvent::PlayerAnimation {
+                        player: index,
+                        event: event.clone(),
+                    }),
+            );
+        }
+
+        self.objects.iter_mut().for_each(|object| {
+            object.body_mut().step(delta_time, map, world_height, 0.0);
+            object.update(delta_time, map, world_height);
+        });
+
+        let current = self.turns.current_player();
+        let mut fire_request = None;
+        for (index, player) in self.players.iter_mut().enumerate() {
+            if player.take_fire_request() && Some(index) == current {
+                fire_request = Some((player.weapon, player.aim_angle));
+            }
+        }
+        if let Some((weapon, angle)) = fire_request {
+            self.fire(weapon, angle, DEFAULT_FIRE_POWER);
+        }
+
+        let map = &self.map;
+
+        let players = &self.players;
+        let wind = self.wind;
+        let detonations: Vec<Detonation> = self
+            .projectiles
+            .iter_mut()
+            .filter_map(|projectile| projectile.update(delta_time, map, players, wind))
+            .collect();
+        self.projectiles.retain(|projectile| !projectile.finished);
+
+        for detonation in detonations {
+            self.explode(detonation.x, detonation.y, detonation.radius);
+            if let Some(callback) = &mut self.on_detonation {
+                callback(&detonation);
+            }
+        }
+
+        self.remove_dead_players();
+
+        if self.turns.update(delta_time, !self.projectiles.is_empty()) && !self.follow_remote_turns
+        {
+            self.end_turn();
+        }
+    }
+}
+
+/// Відкидає тіло від вибуху в (`x`, `y`). Повертає силу удару: 1 в епіцентрі,
+/// 0 на краю; `None`, якщо вибух тіла не дістав.
+fn blast(body: &mut PhysicsBody, x: f64, y: f64, radius: f64) -> Option<f64> {
+    let center = body.center();
+    let (dx, dy) = (center.x - x, center.y - y);
+    let distance = (dx * dx + dy * dy).sqrt();
+    // зачіпає, якщо вибух дістав хоч до краю тіла
+    let reach = radius + body.width.max(body.height) / 2.0;
+    if distance >= reach {
+        return None;
+    }
+    let falloff = 1.0 - distance / reach;
+
+    // відкидаємо від центру і трохи вгору, щоб тіло відірвалося від землі
+    let (nx, ny) = if distance > 0.0 {
+        (dx / distance, dy / distance)
+    } else {
+        (0.0, -1.0)
+    };
+    let speed = EXPLOSION_KNOCKBACK * falloff;
+    body.apply_impulse(nx * speed, (ny - 0.5).clamp(-1.0, -0.5) * speed);
+    Some(falloff)
+}
+
+/// Кут прицілу гравця у світових координатах (з урахуванням, куди він дивиться).
+fn world_angle(player: &Player, angle: f64) -> f64 {
+    if player.facing_left {
+        PI - angle
+    } else {
+        angle
+    }
+}
+
+fn aim_point(player: &Player, distance: f64) -> (f64, f64) {
+    let angle = world_angle(player, player.aim_angle);
+    let center = player.body.center();
+    (
+        center.x + angle.cos() * distance,
+        center.y - angle.sin() * distance,
+    )
+}
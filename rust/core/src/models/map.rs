@@ -0,0 +1,288 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::material::Material;
+use crate::models::position::Position;
+use crate::models::terrain::{self, TerrainPreset};
+use crate::render::{LayerState, Rect, Renderer};
+use crate::tileset::{self, TileSet};
+
+const TERRAIN_LAYER: &str = "terrain";
+
+/// Прямокутник тайлів `[col_start, col_end) x [row_start, row_end)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TileRegion {
+    pub col_start: usize,
+    pub row_start: usize,
+    pub col_end: usize,
+    pub row_end: usize,
+}
+
+impl TileRegion {
+    pub fn union(self, other: TileRegion) -> TileRegion {
+        TileRegion {
+            col_start: self.col_start.min(other.col_start),
+            row_start: self.row_start.min(other.row_start),
+            col_end: self.col_end.max(other.col_end),
+            row_end: self.row_end.max(other.row_end),
+        }
+    }
+}
+
+/// Точка під ногами гравця, де він з'являється. `team: None` — для будь-якої команди.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SpawnPoint {
+    pub x: f64,
+    pub y: f64,
+    #[serde(default)]
+    pub team: Option<usize>,
+}
+
+pub struct Map {
+    pub tile_size: f64,
+    pub data: Vec<Vec<Material>>,
+    pub dirty: Option<TileRegion>,
+    pub water_level: Option<f64>, // y поверхні води в пікселях
+    pub spawn_points: Vec<SpawnPoint>,
+}
+
+impl Map {
+    /// Карта за замовчуванням: пагорби зі сталим seed. Розміри — у пікселях світу.
+    pub fn new(width: f64, height: f64) -> Self {
+        Self::generate(
+            width,
+            height,
+            terrain::DEFAULT_SEED,
+            TerrainPreset::default(),
+        )
+    }
+
+    /// Процедурна карта. Однакові seed, пресет і розмір дають однакову карту на
+    /// кожному клієнті.
+    pub fn generate(width: f64, height: f64, seed: u64, preset: TerrainPreset) -> Self {
+        let tile_size = 5.0;
+        let cols = (width / tile_size).floor() as usize;
+        let rows = (height / tile_size).floor() as usize;
+        let terrain = terrain::generate(seed, cols, rows, &preset.settings());
+
+        let mut map = Self::from_tiles(tile_size, terrain.tiles);
+        map.water_level = terrain.water_row.map(|row| row as f64 * tile_size);
+        map
+    }
+
+    /// Без `tileset` (або поки аркуш не завантажився) тайли заливаються кольором матеріалу.
+    pub fn draw(&self, renderer: &dyn Renderer, tileset: Option<&TileSet>) {
+        self.draw_region(renderer, tileset, self.full_region());
+        self.draw_water(renderer);
+    }
+
+    /// Як [`Map::draw`], але рельєф живе в закешованому шарі рендерера:
+    /// перемальовуються лише тайли з `dirty` (і їхні сусіди, бо від них залежить
+    /// автотайлінг), а на екран щокадру йде один готовий шар.
+    pub fn draw_cached(
+        &self,
+        renderer: &dyn Renderer,
+        tileset: Option<&TileSet>,
+        dirty: Option<TileRegion>,
+    ) {
+        let width = self.cols() as f64 * self.tile_size;
+        let height = self.rows() as f64 * self.tile_size;
+        let region = match renderer.begin_layer(TERRAIN_LAYER, width, height) {
+            LayerState::Unsupported => return self.draw(renderer, tileset),
+            LayerState::Fresh => Some(self.full_region()),
+            LayerState::Cached => dirty.map(|r| self.autotile_neighbourhood(r)),
+        };
+        if let Some(region) = region {
+            renderer.clear_rect(self.region_rect(region));
+            self.draw_region(renderer, tileset, region);
+        }
+        renderer.end_layer();
+        renderer.draw_layer(TERRAIN_LAYER);
+        self.draw_water(renderer);
+    }
+
+    fn draw_region(&self, renderer: &dyn Renderer, tileset: Option<&TileSet>, region: TileRegion) {
+        let rows = self.data[region.row_start..region.row_end].iter();
+        for (row_idx, row) in rows.enumerate().map(|(i, r)| (i + region.row_start, r)) {
+            let cols = row[region.col_start..region.col_end].iter();
+            for (col_idx, &tile) in cols.enumerate().map(|(i, t)| (i + region.col_start, t)) {
+                if tile == Material::Air {
+                    continue;
+                }
+                let x = col_idx as f64 * self.tile_size;
+                let y = row_idx as f64 * self.tile_size;
+                let tile_rect = Rect::new(x, y, self.tile_size, self.tile_size);
+                match tileset {
+                    Some(tileset) if tileset.textures(tile) => {
+                        tileset.draw_tile(renderer, self, col_idx, row_idx, tile_rect)
+                    }
+                    _ => renderer.fill_rect(tile_rect, tile.color()),
+                }
+            }
+        }
+    }
+
+    fn draw_water(&self, renderer: &dyn Renderer) {
+        if let Some(water) = self.water_level {
+            let width = self.cols() as f64 * self.tile_size;
+            let height = self.rows() as f64 * self.tile_size - water;
+            if height > 0.0 {
+                renderer.fill_rect(
+                    Rect::new(0.0, water, width, height),
+                    "rgba(30, 90, 200, 0.6)",
+                );
+            }
+        }
+    }
+
+    pub fn full_region(&self) -> TileRegion {
+        TileRegion {
+            col_start: 0,
+            row_start: 0,
+            col_end: self.cols(),
+            row_end: self.rows(),
+        }
+    }
+
+    fn region_rect(&self, region: TileRegion) -> Rect {
+        Rect::new(
+            region.col_start as f64 * self.tile_size,
+            region.row_start as f64 * self.tile_size,
+            (region.col_end - region.col_start) as f64 * self.tile_size,
+            (region.row_end - region.row_start) as f64 * self.tile_size,
+        )
+    }
+
+    /// Область разом із тайлами, чий вигляд залежить від змінених: сусіди
+    /// збоку й згори, а знизу — на всю глибину текстурного профілю.
+    fn autotile_neighbourhood(&self, region: TileRegion) -> TileRegion {
+        TileRegion {
+            col_start: region.col_start.saturating_sub(1),
+            row_start: region.row_start.saturating_sub(1),
+            col_end: (region.col_end + 1).min(self.cols()),
+            row_end: (region.row_end + tileset::MAX_DEPTH).min(self.rows()),
+        }
+    }
+
+    /// Карта з готової сітки тайлів (рядки зверху вниз).
+    pub fn from_tiles(tile_size: f64, data: Vec<Vec<Material>>) -> Self {
+        let mut map = Self {
+            tile_size,
+            data,
+            dirty: None,
+            water_level: None,
+            spawn_points: Vec::new(),
+        };
+        // нова карта — кеш рельєфу (якщо він є) треба перемалювати повністю
+        map.mark_dirty(map.full_region());
+        map
+    }
+
+    /// Ширина світу в пікселях.
+    pub fn width(&self) -> f64 {
+        self.cols() as f64 * self.tile_size
+    }
+
+    pub fn height(&self) -> f64 {
+        self.rows() as f64 * self.tile_size
+    }
+
+    pub fn rows(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn cols(&self) -> usize {
+        self.data.first().map_or(0, |r| r.len())
+    }
+
+    /// Вирізає кратер: прибирає всі руйнівні тайли, центр яких лежить у колі.
+    /// Повертає кількість знищених тайлів.
+    pub fn carve_circle(&mut self, center_x: f64, center_y: f64, radius: f64) -> usize {
+        if radius <= 0.0 || self.rows() == 0 {
+            return 0;
+        }
+
+        let center = Position::new(center_x, center_y);
+        let to_tile = |v: f64| (v / self.tile_size).floor().max(0.0) as usize;
+        let col_start = to_tile(center_x - radius);
+        let row_start = to_tile(center_y - radius);
+        let col_end = (to_tile(center_x + radius) + 1).min(self.cols());
+        let row_end = (to_tile(center_y + radius) + 1).min(self.rows());
+
+        let mut removed = 0;
+        for row in row_start..row_end {
+            for col in col_start..col_end {
+                let tile_center = Position::new(
+                    (col as f64 + 0.5) * self.tile_size,
+                    (row as f64 + 0.5) * self.tile_size,
+                );
+                if self.data[row][col].is_destructible()
+                    && tile_center.distance_to(&center) <= radius
+                {
+                    self.data[row][col] = Material::Air;
+                    removed += 1;
+                }
+            }
+        }
+
+        if removed > 0 {
+            self.mark_dirty(TileRegion {
+                col_start,
+                row_start,
+                col_end,
+                row_end,
+            });
+        }
+        removed
+    }
+
+    pub fn mark_dirty(&mut self, region: TileRegion) {
+        self.dirty = Some(match self.dirty {
+            Some(existing) => existing.union(region),
+            None => region,
+        });
+    }
+
+    /// Забирає накопичену брудну область, щоб рендер перемалював лише її.
+    pub fn take_dirty(&mut self) -> Option<TileRegion> {
+        self.dirty.take()
+    }
+
+    pub fn is_solid_at(&self, x: f64, y: f64) -> bool {
+        self.material_at(x, y).is_solid()
+    }
+
+    /// Матеріал у точці світу; за межами карти — повітря.
+    pub fn material_at(&self, x: f64, y: f64) -> Material {
+        let col = (x / self.tile_size).floor() as isize;
+        let row = (y / self.tile_size).floor() as isize;
+        self.tile(col, row)
+    }
+
+    pub fn can_move_to(&self, x: f64, y: f64, width: f64, height: f64) -> bool {
+        // Перевіряємо всі тайли, що перекриваються прямокутником
+        let left_tile = (x / self.tile_size).floor() as isize;
+        let right_tile = ((x + width - 1.0) / self.tile_size).floor() as isize;
+        let top_tile = (y / self.tile_size).floor() as isize;
+        let bottom_tile = ((y + height - 1.0) / self.tile_size).floor() as isize;
+
+        for row in top_tile..=bottom_tile {
+            for col in left_tile..=right_tile {
+                if self.tile(col, row).is_solid() {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    pub fn tile(&self, col: isize, row: isize) -> Material {
+        if row < 0 || col < 0 {
+            return Material::Air;
+        }
+        self.data
+            .get(row as usize)
+            .and_then(|r| r.get(col as usize))
+            .copied()
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,283 @@
+//! Завантаження й збереження карт.
+//!
+//! **Маска з картинки.** Кожен піксель — один тайл; прозорий піксель
+//! (альфа `< ALPHA_THRESHOLD`) — повітря. Непрозорий стає матеріалом, чий
+//! [`Material::mask_color`] збігається з кольором пікселя, інакше — землею.
+//! Картинку розпаковує браузер (через canvas), сюди приходять сирі RGBA-байти.
+//!
+//! **JSON-формат.** Рядки тайлів зверху вниз; символ тайлу перекладається
+//! через `legend` у матеріал (`air`, `dirt`, `rock`, `ice`, `bouncy`, `lava`, `water`):
+//!
+//! ```json
+//! {
+//!   "version": 2,
+//!   "name": "Острів",
+//!   "tile_size": 5,
+//!   "legend": { ".": "air", "#": "dirt", "R": "rock" },
+//!   "tiles": [
+//!     "........",
+//!     "..####..",
+//!     "RRRRRRRR"
+//!   ],
+//!   "spawn_points": [{ "x": 12, "y": 0, "team": 0 }],
+//!   "water_level": 15,
+//!   "metadata": { "author": "Оля" }
+//! }
+//! ```
+//!
+//! `legend`, `spawn_points`, `water_level` і `metadata` необов'язкові (без
+//! `legend` діють символи з [`default_legend`]);
+//! координати точок появи й рівень води — у пікселях світу.
+//!
+//! Файли версії 1 з числами в `legend` теж читаються: `0` — повітря,
+//! будь-яке інше число — земля.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::models::map::{Map, SpawnPoint};
+use crate::models::material::Material;
+
+pub const MAP_FORMAT_VERSION: u32 = 2; // 2: у legend назви матеріалів замість чисел
+pub const ALPHA_THRESHOLD: u8 = 128;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MapFile {
+    pub version: u32,
+    #[serde(default)]
+    pub name: String,
+    pub tile_size: f64,
+    #[serde(default = "default_legend")]
+    pub legend: BTreeMap<char, Material>,
+    pub tiles: Vec<String>,
+    #[serde(default)]
+    pub spawn_points: Vec<SpawnPoint>,
+    #[serde(default)]
+    pub water_level: Option<f64>,
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+}
+
+pub fn default_legend() -> BTreeMap<char, Material> {
+    BTreeMap::from([
+        ('.', Material::Air),
+        (' ', Material::Air),
+        ('#', Material::Dirt),
+        ('R', Material::Rock),
+        ('I', Material::Ice),
+        ('B', Material::Bouncy),
+        ('L', Material::Lava),
+        ('W', Material::Water),
+    ])
+}
+
+#[derive(Debug)]
+pub enum MapError {
+    Malformed(String),
+    UnsupportedVersion(u32),
+    /// Розмір тайла має бути скінченним і додатним.
+    BadTileSize(f64),
+    UnknownTile {
+        row: usize,
+        symbol: char,
+    },
+    RaggedRows {
+        row: usize,
+        expected: usize,
+        got: usize,
+    },
+    BadImage {
+        width: usize,
+        height: usize,
+        bytes: usize,
+    },
+}
+
+impl std::fmt::Display for MapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MapError::Malformed(reason) => write!(f, "malformed map: {}", reason),
+            MapError::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported map version {} (expected {})",
+                version, MAP_FORMAT_VERSION
+            ),
+            MapError::BadTileSize(size) => {
+                write!(f, "tile size {} must be positive and finite", size)
+            }
+            MapError::UnknownTile { row, symbol } => {
+                write!(f, "unknown tile '{}' in row {}", symbol, row)
+            }
+            MapError::RaggedRows { row, expected, got } => {
+                write!(f, "row {} has {} tiles, expected {}", row, got, expected)
+            }
+            MapError::BadImage {
+                width,
+                height,
+                bytes,
+            } => write!(
+                f,
+                "image {}x{} needs {} RGBA bytes, got {}",
+                width,
+                height,
+                width * height * 4,
+                bytes
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MapError {}
+
+impl MapFile {
+    pub fn parse(text: &str) -> Result<Self, MapError> {
+        let mut json: Value =
+            serde_json::from_str(text).map_err(|e| MapError::Malformed(e.to_string()))?;
+        if json["version"].as_u64() == Some(1) {
+            upgrade_v1(&mut json);
+        }
+        let file: MapFile =
+            serde_json::from_value(json).map_err(|e| MapError::Malformed(e.to_string()))?;
+        if file.version != MAP_FORMAT_VERSION {
+            return Err(MapError::UnsupportedVersion(file.version));
+        }
+        Ok(file)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("map files are always serializable")
+    }
+
+    pub fn into_map(self) -> Result<Map, MapError> {
+        check_tile_size(self.tile_size)?;
+        let width = self.tiles.first().map_or(0, |r| r.chars().count());
+        let mut data = Vec::with_capacity(self.tiles.len());
+        for (row, line) in self.tiles.iter().enumerate() {
+            let tiles = line
+                .chars()
+                .map(|symbol| {
+                    self.legend
+                        .get(&symbol)
+                        .copied()
+                        .ok_or(MapError::UnknownTile { row, symbol })
+                })
+                .collect::<Result<Vec<Material>, _>>()?;
+            if tiles.len() != width {
+                return Err(MapError::RaggedRows {
+                    row,
+                    expected: width,
+                    got: tiles.len(),
+                });
+            }
+            data.push(tiles);
+        }
+
+        let mut map = Map::from_tiles(self.tile_size, data);
+        map.spawn_points = self.spawn_points;
+        map.water_level = self.water_level;
+        Ok(map)
+    }
+
+    /// Знімок поточної карти (разом з кратерами) у формат файлу.
+    pub fn from_map(map: &Map, name: &str) -> Self {
+        let mut legend = default_legend();
+        legend.remove(&' ');
+        let symbol_for = |tile: Material| {
+            legend
+                .iter()
+                .find(|(_, t)| **t == tile)
+                .map_or('#', |(s, _)| *s)
+        };
+        Self {
+            version: MAP_FORMAT_VERSION,
+            name: name.to_string(),
+            tile_size: map.tile_size,
+            tiles: map
+                .data
+                .iter()
+                .map(|row| row.iter().map(|&t| symbol_for(t)).collect())
+                .collect(),
+            legend,
+            spawn_points: map.spawn_points.clone(),
+            water_level: map.water_level,
+            metadata: BTreeMap::new(),
+        }
+    }
+}
+
+/// Версія 1 знала лише повітря (`0`) і землю (будь-яке інше число).
+fn upgrade_v1(json: &mut Value) {
+    if let Some(legend) = json.get_mut("legend").and_then(Value::as_object_mut) {
+        for tile in legend.values_mut() {
+            let material = match tile.as_u64() {
+                Some(0) => Material::Air,
+                _ => Material::Dirt,
+            };
+            *tile = serde_json::to_value(material).expect("materials are always serializable");
+        }
+    }
+    json["version"] = MAP_FORMAT_VERSION.into();
+}
+
+/// Карта з RGBA-пікселів картинки: один піксель — один тайл.
+pub fn from_alpha_mask(
+    width: usize,
+    height: usize,
+    rgba: &[u8],
+    tile_size: f64,
+) -> Result<Map, MapError> {
+    check_tile_size(tile_size)?;
+    if rgba.len() != width * height * 4 {
+        return Err(MapError::BadImage {
+            width,
+            height,
+            bytes: rgba.len(),
+        });
+    }
+    if width == 0 {
+        return Ok(Map::from_tiles(tile_size, Vec::new()));
+    }
+    let data = rgba
+        .chunks(width * 4)
+        .take(height)
+        .map(|row| {
+            row.chunks(4)
+                .map(|pixel| material_for_pixel([pixel[0], pixel[1], pixel[2], pixel[3]]))
+                .collect()
+        })
+        .collect();
+    Ok(Map::from_tiles(tile_size, data))
+}
+
+fn check_tile_size(tile_size: f64) -> Result<(), MapError> {
+    if tile_size <= 0.0 || !tile_size.is_finite() {
+        return Err(MapError::BadTileSize(tile_size));
+    }
+    Ok(())
+}
+
+fn material_for_pixel([r, g, b, a]: [u8; 4]) -> Material {
+    if a < ALPHA_THRESHOLD {
+        return Material::Air;
+    }
+    Material::ALL
+        .iter()
+        .copied()
+        .find(|m| *m != Material::Air && m.mask_color() == [r, g, b])
+        .unwrap_or(Material::Dirt)
+}
+
+/// Зворотне до [`from_alpha_mask`]: кожен матеріал — непрозорий піксель свого кольору.
+pub fn to_alpha_mask(map: &Map) -> Vec<u8> {
+    map.data
+        .iter()
+        .flatten()
+        .flat_map(|&tile| {
+            let [r, g, b] = tile.mask_color();
+            let a = if tile == Material::Air { 0 } else { 255 };
+            [r, g, b, a]
+        })
+        .collect()
+}
@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+
+/// З чого зроблений тайл карти.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Material {
+    #[default]
+    Air,
+    Dirt,
+    Rock, // вибухи його не беруть
+    Ice,
+    Bouncy,
+    Lava,
+    Water,
+}
+
+impl Material {
+    pub const ALL: [Material; 7] = [
+        Material::Air,
+        Material::Dirt,
+        Material::Rock,
+        Material::Ice,
+        Material::Bouncy,
+        Material::Lava,
+        Material::Water,
+    ];
+
+    /// Чи зупиняє тайл гравців і снаряди. Рідини пропускають.
+    pub fn is_solid(&self) -> bool {
+        matches!(
+            self,
+            Material::Dirt | Material::Rock | Material::Ice | Material::Bouncy
+        )
+    }
+
+    pub fn is_liquid(&self) -> bool {
+        matches!(self, Material::Lava | Material::Water)
+    }
+
+    pub fn is_destructible(&self) -> bool {
+        self.is_solid() && *self != Material::Rock
+    }
+
+    /// Яку частку горизонтальної швидкості забирає дотик до поверхні.
+    pub fn friction(&self) -> f64 {
+        match self {
+            Material::Dirt | Material::Bouncy => 0.2,
+            Material::Rock => 0.3,
+            Material::Ice => 0.02,
+            Material::Air | Material::Lava | Material::Water => 0.0,
+        }
+    }
+
+    /// Наскільки поверхня сама відкидає назад (0 — зовсім ні).
+    pub fn restitution(&self) -> f64 {
+        match self {
+            Material::Bouncy => 0.85,
+            _ => 0.0,
+        }
+    }
+
+    /// Скільки здоров'я за секунду забирає рідина тому, хто в ній стоїть.
+    pub fn damage_per_second(&self) -> f64 {
+        match self {
+            Material::Lava => 200.0, // згоряє за пів секунди
+            Material::Water => 25.0, // тоне за чотири
+            _ => 0.0,
+        }
+    }
+
+    /// Заливка тайлу, поки для матеріалу немає текстури.
+    pub fn color(&self) -> &'static str {
+        match self {
+            Material::Air => "transparent",
+            Material::Dirt => "green",
+            Material::Rock => "dimgray",
+            Material::Ice => "lightcyan",
+            Material::Bouncy => "hotpink",
+            Material::Lava => "orangered",
+            Material::Water => "royalblue",
+        }
+    }
+
+    /// Колір пікселя в PNG-масці карти.
+    pub fn mask_color(&self) -> [u8; 3] {
+        match self {
+            Material::Air => [0, 0, 0],
+            Material::Dirt => [120, 80, 40],
+            Material::Rock => [128, 128, 128],
+            Material::Ice => [200, 240, 255],
+            Material::Bouncy => [255, 0, 255],
+            Material::Lava => [255, 80, 0],
+            Material::Water => [0, 80, 255],
+        }
+    }
+}
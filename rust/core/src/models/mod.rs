@@ -0,0 +1,13 @@
+pub mod player;
+pub mod map;
+pub mod game;
+pub mod event;
+pub mod position;
+pub mod projectile;
+pub mod traits;
+pub mod turn;
+pub mod terrain;
+pub mod map_file;
+pub mod material;
+pub mod body;
+pub mod spawn;
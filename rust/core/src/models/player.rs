@@ -0,0 +1,372 @@
+use std::rc::Rc;
+
+use crate::animation::{Animation, AnimationSet};
+use crate::assets::CHARACTER_SHADOW;
+use crate::input::{Action, InputState};
+use crate::models::position::Position;
+use crate::models::projectile::WeaponKind;
+
+use crate::models::body::PhysicsBody;
+use crate::models::map::Map;
+use crate::models::traits::{AnimatedObject, CanvasObject, HasBody};
+use crate::render::{Rect, Renderer};
+use std::collections::HashSet;
+use std::f64::consts::FRAC_PI_2;
+
+pub use crate::models::body::{AIR_ACCELERATION, GRAVITY, WALK_ACCELERATION, WALK_SPEED};
+
+pub const DEFAULT_STEP_HEIGHT: f64 = 12.0; // px, на такий уступ заходимо без стрибка
+pub const JUMP_SPEED: f64 = 600.0; // px/s
+pub const AIM_SPEED: f64 = 1.5; // рад/с
+pub const MAX_HEALTH: f64 = 100.0;
+const HURT_TIME: f64 = 0.3; // с, скільки грає анімація болю
+pub const SPAWN_TIME: f64 = 0.4; // с, анімація появи: 4 кадри по 0.1 с; до кінця — без керування
+
+// Тінь character_Shadow_15x4.png під кадром персонажа 20x20
+const CHARACTER_FRAME: f64 = 20.0;
+const SHADOW_SPRITE_WIDTH: f64 = 15.0;
+const SHADOW_SPRITE_HEIGHT: f64 = 4.0;
+const SHADOW_FADE_HEIGHT: f64 = 400.0; // px над землею, де тінь стискається до мінімуму
+const SHADOW_MIN_SCALE: f64 = 0.3;
+
+/// Кліпи й переходи персонажа Nuclear Leak (20x20, 6 рядків).
+pub const NUCLEAR_LEAK_ANIMATIONS: &str = include_str!("../animations/nuclear_leak.json");
+
+/// Анімація персонажа зі спрайтшита Nuclear Leak; `sprite` — ключ, напр.
+/// [`crate::assets::character_key`].
+pub fn character_animation(sprite: &str) -> Animation {
+    NUCLEAR_LEAK_SET.with(|set| Animation::new(sprite, Rc::clone(set)))
+}
+
+thread_local! {
+    // розбираємо один раз; усі персонажі ділять набір через `Rc`
+    static NUCLEAR_LEAK_SET: Rc<AnimationSet> = Rc::new(
+        AnimationSet::from_json(NUCLEAR_LEAK_ANIMATIONS).expect("built-in animation set is valid"),
+    );
+}
+
+/// Верх першого твердого тайла на висоті `y` або нижче.
+fn ground_below(map: &Map, x: f64, y: f64) -> Option<f64> {
+    let mut y = y;
+    while y < map.height() {
+        if map.is_solid_at(x, y) {
+            return Some((y / map.tile_size).floor() * map.tile_size);
+        }
+        y += map.tile_size;
+    }
+    None
+}
+
+/// Наскільки боляче падати: до `min_speed` безпечно, далі шкода росте
+/// лінійно зі швидкістю удару, але не більше `max_damage`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FallDamage {
+    pub min_speed: f64,        // px/s
+    pub damage_per_speed: f64, // hp за кожен px/s понад поріг
+    pub max_damage: f64,
+}
+
+impl FallDamage {
+    pub fn damage(&self, impact_speed: f64) -> f64 {
+        ((impact_speed - self.min_speed) * self.damage_per_speed).clamp(0.0, self.max_damage)
+    }
+}
+
+impl Default for FallDamage {
+    fn default() -> Self {
+        Self {
+            min_speed: 900.0,
+            damage_per_speed: 0.05,
+            max_damage: 50.0,
+        }
+    }
+}
+
+/// Приземлення після польоту, зафіксоване тілом у `try_move_y`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Landing {
+    pub impact_speed: f64, // px/s
+    pub damage: f64,
+}
+
+pub struct Player {
+    pub body: PhysicsBody,
+    pub health: f64,
+    pub fall_damage: FallDamage,
+    pub horizontal_offset: f64,
+    pub animation: Option<Animation>,
+    pub input: InputState,
+    pub facing_left: bool,
+    pub aim_angle: f64, // радіани від горизонталі, додатний — вгору
+    pub weapon: WeaponKind,
+    fire_requested: bool,
+    hurt_timer: f64,
+    spawn_timer: f64,
+    landing: Option<Landing>,
+    just_landed: bool, // приземлився на цьому кроці — для анімації
+}
+impl CanvasObject for Player {
+    fn draw(&self, renderer: &dyn Renderer) {
+        self.draw_interpolated(renderer, 1.0);
+    }
+
+    fn update(&mut self, delta_time: f64, map: &Map, canvas_height: f64) {
+        let is_on_ground = self.body.is_grounded(map, canvas_height);
+
+        if !self.is_alive() {
+            // мертвий лише падає і дограває анімацію смерті
+            self.input.clear();
+            self.body.step(delta_time, map, canvas_height, 0.0);
+            self.animate(delta_time, false, is_on_ground);
+            return;
+        }
+        self.take_liquid_damage(delta_time, map);
+        if self.spawn_timer > 0.0 {
+            // з'являємось: натискання за цей час не рахуються
+            self.spawn_timer -= delta_time;
+            self.input.end_step();
+            self.body.step(delta_time, map, canvas_height, 0.0);
+            self.animate(delta_time, false, is_on_ground);
+            return;
+        }
+        self.hurt_timer = (self.hurt_timer - delta_time).max(0.0);
+        let moving_left = self.input.is_held(Action::MoveLeft);
+        let moving_right = self.input.is_held(Action::MoveRight);
+        let is_moving = moving_left != moving_right;
+        let direction = match (moving_left, moving_right) {
+            (true, false) => -1.0,
+            (false, true) => 1.0,
+            _ => 0.0,
+        };
+        if direction != 0.0 {
+            self.facing_left = direction < 0.0;
+        }
+
+        if self.input.was_pressed(Action::Jump) && is_on_ground {
+            self.jump(map, canvas_height);
+        }
+
+        if self.input.is_held(Action::AimUp) {
+            self.aim_angle = (self.aim_angle + AIM_SPEED * delta_time).min(FRAC_PI_2);
+        }
+        if self.input.is_held(Action::AimDown) {
+            self.aim_angle = (self.aim_angle - AIM_SPEED * delta_time).max(-FRAC_PI_2);
+        }
+        if self.input.was_pressed(Action::SwitchWeapon) {
+            self.weapon = self.weapon.next();
+        }
+        if self.input.was_pressed(Action::Fire) {
+            self.fire_requested = true;
+        }
+        self.input.end_step();
+
+        if let Some(impact_speed) = self.body.step(delta_time, map, canvas_height, direction) {
+            self.land(impact_speed);
+        }
+        self.animate(delta_time, is_moving, is_on_ground);
+    }
+}
+
+impl HasBody for Player {
+    fn body(&self) -> &PhysicsBody {
+        &self.body
+    }
+
+    fn body_mut(&mut self) -> &mut PhysicsBody {
+        &mut self.body
+    }
+}
+
+impl AnimatedObject for Player {
+    fn set_animation(&mut self, animation: Animation) {
+        self.animation = Some(animation);
+    }
+
+    /// Стан гравця як прапорці для переходів у `nuclear_leak.json`.
+    fn update_animation_state(&mut self, is_moving: bool, is_on_ground: bool) {
+        let conditions = [
+            ("dead", !self.is_alive()),
+            ("spawning", self.is_spawning()),
+            ("hurt", self.hurt_timer > 0.0),
+            ("landed", std::mem::take(&mut self.just_landed)),
+            ("on_ground", is_on_ground),
+            ("rising", self.body.velocity_y < 0.0),
+            ("moving", is_moving),
+        ];
+        let flags: Vec<&str> = conditions
+            .iter()
+            .filter(|(_, on)| *on)
+            .map(|(flag, _)| *flag)
+            .collect();
+        if let Some(anim) = &mut self.animation {
+            anim.apply_transitions(&flags);
+        }
+    }
+}
+
+impl Player {
+    /// Тіло приземлилось після польоту: рахуємо шкоду від падіння і
+    /// показуємо кадр приземлення.
+    fn land(&mut self, impact_speed: f64) {
+        let damage = self.fall_damage.damage(impact_speed);
+        self.take_damage(damage);
+        self.landing = Some(Landing {
+            impact_speed,
+            damage,
+        });
+        self.just_landed = true;
+    }
+
+    /// Лава пече, вода топить; смерть від них підбирає `Game` як і будь-яку іншу.
+    fn take_liquid_damage(&mut self, delta_time: f64, map: &Map) {
+        let damage = self.body.submerged_in(map).damage_per_second() * delta_time;
+        self.take_damage(damage);
+    }
+
+    /// Останнє приземлення, якщо воно ще не було забране.
+    pub fn take_landing(&mut self) -> Option<Landing> {
+        self.landing.take()
+    }
+
+    pub fn new(position: Position, animation: Option<Animation>) -> Self {
+        let mut body = PhysicsBody::new(position, 64.0, 64.0);
+        body.step_height = DEFAULT_STEP_HEIGHT;
+        Self {
+            body,
+            health: MAX_HEALTH,
+            fall_damage: FallDamage::default(),
+            horizontal_offset: 22.0,
+            animation,
+            input: InputState::default(),
+            facing_left: false,
+            aim_angle: 0.0,
+            weapon: WeaponKind::Bazooka,
+            fire_requested: false,
+            hurt_timer: 0.0,
+            spawn_timer: 0.0,
+            landing: None,
+            just_landed: false,
+        }
+    }
+
+    /// Починає анімацію появи; керування з'явиться, коли вона дограє.
+    pub fn start_spawn(&mut self) {
+        self.spawn_timer = SPAWN_TIME;
+        self.input.clear();
+        self.update_animation_state(false, true);
+    }
+
+    pub fn is_spawning(&self) -> bool {
+        self.spawn_timer > 0.0
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.health > 0.0
+    }
+
+    /// Віднімає здоров'я; повертає `true`, якщо цей удар гравця вбив.
+    pub fn take_damage(&mut self, amount: f64) -> bool {
+        if !self.is_alive() || amount <= 0.0 {
+            return false;
+        }
+        self.health = (self.health - amount).max(0.0);
+        self.hurt_timer = HURT_TIME;
+        !self.is_alive()
+    }
+
+    /// Здоров'я з мережевого знімка. Приймаємо й більше за поточне, а
+    /// анімацію болю вмикаємо, лише коли здоров'я справді впало.
+    pub fn sync_health(&mut self, health: f64) {
+        if health < self.health {
+            self.hurt_timer = HURT_TIME;
+        }
+        self.health = health;
+    }
+
+    /// Малює гравця між попередньою і поточною позицією; `alpha` — частка
+    /// незавершеного кроку симуляції (0.0..1.0).
+    pub fn draw_interpolated(&self, renderer: &dyn Renderer, alpha: f64) {
+        let (width, height) = (self.body.width, self.body.height);
+        let position = self.body.previous_position.lerp(&self.body.position, alpha);
+        if let Some(anim) = &self.animation {
+            let dest = Rect::new(
+                position.x - self.horizontal_offset,
+                position.y,
+                width + self.horizontal_offset * 2.0,
+                height,
+            );
+            anim.draw(renderer, dest, self.facing_left);
+        } else {
+            let body = Rect::new(position.x, position.y, width, height);
+            renderer.fill_rect(body, "blue");
+        }
+        if self.is_alive() {
+            self.draw_health_bar(renderer, position);
+        }
+    }
+
+    /// Тінь на першому твердому тайлі під черв'яком: що вище він над землею,
+    /// то вона менша. Над прірвою тіні немає.
+    pub fn shadow_rect(&self, map: &Map, alpha: f64) -> Option<Rect> {
+        let position = self.body.previous_position.lerp(&self.body.position, alpha);
+        let center_x = position.x + self.body.width / 2.0;
+        let feet_y = position.y + self.body.height;
+        let ground_y = ground_below(map, center_x, feet_y)?;
+
+        let scale = (1.0 - (ground_y - feet_y) / SHADOW_FADE_HEIGHT).max(SHADOW_MIN_SCALE);
+        let sprite_width = self.body.width + self.horizontal_offset * 2.0;
+        let width = sprite_width * SHADOW_SPRITE_WIDTH / CHARACTER_FRAME * scale;
+        let height = self.body.height * SHADOW_SPRITE_HEIGHT / CHARACTER_FRAME * scale;
+        Some(Rect::new(
+            center_x - width / 2.0,
+            ground_y - height / 2.0,
+            width,
+            height,
+        ))
+    }
+
+    pub fn draw_shadow(&self, renderer: &dyn Renderer, map: &Map, alpha: f64) {
+        if let Some(dest) = self.shadow_rect(map, alpha) {
+            let source = Rect::new(0.0, 0.0, SHADOW_SPRITE_WIDTH, SHADOW_SPRITE_HEIGHT);
+            renderer.draw_sprite(CHARACTER_SHADOW, source, dest, false);
+        }
+    }
+
+    fn draw_health_bar(&self, renderer: &dyn Renderer, position: Position) {
+        const BAR_HEIGHT: f64 = 4.0;
+        const BAR_GAP: f64 = 6.0;
+        let share = self.health / MAX_HEALTH;
+        let color = if share > 0.6 {
+            "limegreen"
+        } else if share > 0.3 {
+            "gold"
+        } else {
+            "red"
+        };
+        let y = position.y - BAR_GAP - BAR_HEIGHT;
+        let width = self.body.width;
+        renderer.fill_rect(Rect::new(position.x, y, width, BAR_HEIGHT), "black");
+        renderer.fill_rect(Rect::new(position.x, y, width * share, BAR_HEIGHT), color);
+    }
+
+    pub fn jump(&mut self, map: &Map, canvas_height: f64) {
+        if self.body.is_grounded(map, canvas_height) {
+            self.body.velocity_y = -JUMP_SPEED;
+        }
+    }
+    fn animate(&mut self, delta_time: f64, is_moving: bool, is_on_ground: bool) {
+        self.update_animation_state(is_moving, is_on_ground);
+        if let Some(anim) = &mut self.animation {
+            anim.update(delta_time);
+        }
+    }
+
+    pub fn set_actions(&mut self, actions: HashSet<Action>) {
+        self.input.set_held(actions);
+    }
+
+    /// Чи просив гравець вистрілити з останнього виклику.
+    pub fn take_fire_request(&mut self) -> bool {
+        std::mem::take(&mut self.fire_requested)
+    }
+}
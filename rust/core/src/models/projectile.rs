@@ -0,0 +1,217 @@
+use crate::models::map::Map;
+use crate::models::material::Material;
+use crate::models::player::Player;
+use crate::models::position::Position;
+use crate::render::Renderer;
+
+const GRAVITY: f64 = 600.0; // px/s²
+const ARM_TIME: f64 = 0.15; // секунди, поки снаряд не чіпляє гравців (щоб не влучити в себе)
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WeaponKind {
+    Bazooka,
+    Grenade,
+}
+
+pub struct WeaponSpec {
+    pub max_speed: f64,
+    pub explosion_radius: f64,
+    pub fuse: Option<f64>,
+    pub bounciness: f64,
+    pub wind_factor: f64,
+    pub radius: f64,
+}
+
+impl WeaponKind {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "bazooka" => Some(WeaponKind::Bazooka),
+            "grenade" => Some(WeaponKind::Grenade),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WeaponKind::Bazooka => "bazooka",
+            WeaponKind::Grenade => "grenade",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            WeaponKind::Bazooka => WeaponKind::Grenade,
+            WeaponKind::Grenade => WeaponKind::Bazooka,
+        }
+    }
+
+    pub fn spec(&self) -> WeaponSpec {
+        match self {
+            WeaponKind::Bazooka => WeaponSpec {
+                max_speed: 900.0,
+                explosion_radius: 35.0,
+                fuse: None,
+                bounciness: 0.0,
+                wind_factor: 1.0,
+                radius: 3.0,
+            },
+            WeaponKind::Grenade => WeaponSpec {
+                max_speed: 700.0,
+                explosion_radius: 30.0,
+                fuse: Some(3.0),
+                bounciness: 0.45,
+                wind_factor: 0.0,
+                radius: 3.0,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Detonation {
+    pub x: f64,
+    pub y: f64,
+    pub radius: f64,
+    pub weapon: WeaponKind,
+}
+
+pub type DetonationCallback = Box<dyn FnMut(&Detonation)>;
+
+/// Снаряд рахує політ сам, без `PhysicsBody`: він точковий, його зносить
+/// вітер, а від першого дотику до землі чи гравця він вибухає або
+/// відскакує — не стоїть, не ходить і не отримує шкоди від падіння.
+pub struct Projectile {
+    pub kind: WeaponKind,
+    pub position: Position,
+    pub previous_position: Position,
+    pub velocity_x: f64,
+    pub velocity_y: f64,
+    pub age: f64,
+    pub finished: bool,
+}
+
+impl Projectile {
+    /// `angle` у радіанах від горизонталі (додатний — вгору), `power` від 0.0 до 1.0.
+    pub fn launch(kind: WeaponKind, x: f64, y: f64, angle: f64, power: f64) -> Self {
+        let speed = kind.spec().max_speed * power.clamp(0.0, 1.0);
+        Self {
+            kind,
+            position: Position::new(x, y),
+            previous_position: Position::new(x, y),
+            velocity_x: angle.cos() * speed,
+            velocity_y: -angle.sin() * speed,
+            age: 0.0,
+            finished: false,
+        }
+    }
+
+    /// Один тік польоту. Повертає детонацію, якщо снаряд вибухнув.
+    pub fn update(
+        &mut self,
+        delta_time: f64,
+        map: &Map,
+        players: &[Player],
+        wind: f64,
+    ) -> Option<Detonation> {
+        if self.finished {
+            return None;
+        }
+
+        let spec = self.kind.spec();
+        self.age += delta_time;
+        self.previous_position = self.position;
+
+        if let Some(fuse) = spec.fuse {
+            if self.age >= fuse {
+                return self.detonate();
+            }
+        }
+
+        self.velocity_x += wind * spec.wind_factor * delta_time;
+        self.velocity_y += GRAVITY * delta_time;
+
+        // субкроки не довші за пів тайла, щоб снаряд не пролітав крізь тонкі платформи
+        let dx = self.velocity_x * delta_time;
+        let dy = self.velocity_y * delta_time;
+        let max_step = map.tile_size / 2.0;
+        let steps = (dx.abs().max(dy.abs()) / max_step).ceil().max(1.0) as usize;
+
+        for _ in 0..steps {
+            let next_x = self.position.x + dx / steps as f64;
+            let next_y = self.position.y + dy / steps as f64;
+
+            // загиблі ще лежать у `players`, поки грає анімація смерті, — крізь них летимо
+            let hit = players
+                .iter()
+                .filter(|p| p.is_alive())
+                .any(|p| hits_player(p, next_x, next_y));
+            if self.age >= ARM_TIME && hit {
+                self.position = Position::new(next_x, next_y);
+                return self.detonate();
+            }
+
+            if map.is_solid_at(next_x, next_y) {
+                if spec.bounciness <= 0.0 {
+                    self.position = Position::new(next_x, next_y);
+                    return self.detonate();
+                }
+                let surface = map.material_at(next_x, next_y);
+                self.bounce(map, next_x, next_y, spec.bounciness, surface);
+                return None;
+            }
+
+            self.position = Position::new(next_x, next_y);
+        }
+
+        let map_width = map.cols() as f64 * map.tile_size;
+        let map_height = map.rows() as f64 * map.tile_size;
+        if self.position.x < 0.0 || self.position.x > map_width || self.position.y > map_height {
+            // вилетів за межі карти — просто зникає
+            self.finished = true;
+        }
+
+        None
+    }
+
+    fn bounce(&mut self, map: &Map, next_x: f64, next_y: f64, bounciness: f64, surface: Material) {
+        // відбиваємо ту складову швидкості, по якій стикнулися з тайлом;
+        // якщо влучили точно в кут — обидві
+        let bounciness = (bounciness + surface.restitution()).min(0.95);
+        let hit_x = map.is_solid_at(next_x, self.position.y);
+        let hit_y = map.is_solid_at(self.position.x, next_y);
+        if hit_x || !hit_y {
+            self.velocity_x = -self.velocity_x * bounciness;
+        }
+        if hit_y || !hit_x {
+            self.velocity_y = -self.velocity_y * bounciness;
+            self.velocity_x *= 1.0 - surface.friction();
+        }
+    }
+
+    fn detonate(&mut self) -> Option<Detonation> {
+        self.finished = true;
+        Some(Detonation {
+            x: self.position.x,
+            y: self.position.y,
+            radius: self.kind.spec().explosion_radius,
+            weapon: self.kind,
+        })
+    }
+
+    pub fn draw(&self, renderer: &dyn Renderer, alpha: f64) {
+        let position = self.previous_position.lerp(&self.position, alpha);
+        let fill = match self.kind {
+            WeaponKind::Bazooka => "black",
+            WeaponKind::Grenade => "darkolivegreen",
+        };
+        renderer.fill_circle(position.x, position.y, self.kind.spec().radius, fill);
+    }
+}
+
+fn hits_player(player: &Player, x: f64, y: f64) -> bool {
+    let body = &player.body;
+    x >= body.position.x
+        && x <= body.position.x + body.width
+        && y >= body.position.y
+        && y <= body.position.y + body.height
+}
@@ -0,0 +1,86 @@
+//! Де ставити черв'яків, коли карта не задає точок появи: лише туди, де тіло
+//! вміщається, під ногами твердий тайл і немає рідини, а команди — кожна у
+//! своїй частині карти і якнайдалі одна від одної.
+
+use crate::models::map::Map;
+use crate::models::position::Position;
+
+/// Усі позиції (лівий верхній кут), де тіло `width` x `height` може стояти.
+/// Перевіряємо кожну колонку тайлів і кожну поверхню в ній, тож печери й
+/// платформи теж підходять.
+pub fn standing_spots(map: &Map, width: f64, height: f64) -> Vec<Position> {
+    let tile = map.tile_size;
+    let mut spots = Vec::new();
+    for col in 0..map.cols() {
+        let x = col as f64 * tile;
+        if x + width > map.width() {
+            break;
+        }
+        for row in 1..map.rows() {
+            let (c, r) = (col as isize, row as isize);
+            if !map.tile(c, r).is_solid() || map.tile(c, r - 1).is_solid() {
+                continue;
+            }
+            let y = row as f64 * tile - height;
+            if y >= 0.0 && is_safe(map, x, y, width, height) {
+                spots.push(Position::new(x, y));
+            }
+        }
+    }
+    spots
+}
+
+fn is_safe(map: &Map, x: f64, y: f64, width: f64, height: f64) -> bool {
+    let feet = Position::new(x + width / 2.0, y + height);
+    let under_water = map.water_level.is_some_and(|water| feet.y > water);
+    map.can_move_to(x, y, width, height)
+        && map.is_solid_at(x, feet.y + 1.0)
+        && map.is_solid_at(x + width - 1.0, feet.y + 1.0)
+        && !map.material_at(feet.x, feet.y - 1.0).is_liquid()
+        && !under_water
+}
+
+/// Вибирає місце для тіла команди `team` з `team_count`: у своїй смузі карти
+/// (якщо там є місце), найдалі від уже зайнятих позицій `taken`. Першого в
+/// порожній смузі ставимо ближче до її середини. Результат детермінований —
+/// усі клієнти отримають ті самі позиції.
+pub fn choose_spot(
+    map: &Map,
+    spots: &[Position],
+    taken: &[Position],
+    team: usize,
+    team_count: usize,
+    width: f64,
+) -> Option<Position> {
+    let sector_width = map.width() / team_count.max(1) as f64;
+    let sector_start = team as f64 * sector_width;
+    let in_sector = |spot: &&Position| {
+        let center = spot.x + width / 2.0;
+        center >= sector_start && center < sector_start + sector_width
+    };
+    let free = |spot: &&Position| taken.iter().all(|t| (t.x - spot.x).abs() >= width);
+
+    let own: Vec<&Position> = spots.iter().filter(in_sector).filter(free).collect();
+    let candidates = if own.is_empty() {
+        spots.iter().filter(free).collect()
+    } else {
+        own
+    };
+
+    let sector_center = sector_start + sector_width / 2.0;
+    let score = |spot: &Position| {
+        let nearest = taken
+            .iter()
+            .map(|t| t.distance_to(spot))
+            .fold(f64::INFINITY, f64::min);
+        if nearest.is_finite() {
+            nearest
+        } else {
+            -(spot.x + width / 2.0 - sector_center).abs()
+        }
+    };
+    candidates
+        .into_iter()
+        .copied()
+        .max_by(|a, b| score(a).total_cmp(&score(b)))
+}
@@ -0,0 +1,242 @@
+//! Процедурний генератор рельєфу. Уся математика — лише додавання й множення
+//! над власним хешем, без `sin`/`exp` і без стороннього ГВЧ, тож однаковий
+//! seed дає побітово однакову карту і в браузері, і на сервері.
+
+use crate::models::material::Material;
+
+pub const DEFAULT_SEED: u64 = 0x0057_4152_4152_4152;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TerrainPreset {
+    #[default]
+    Hills,
+    Islands,
+    Caves,
+    Floating,
+}
+
+impl TerrainPreset {
+    pub const ALL: [TerrainPreset; 4] = [
+        TerrainPreset::Hills,
+        TerrainPreset::Islands,
+        TerrainPreset::Caves,
+        TerrainPreset::Floating,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TerrainPreset::Hills => "hills",
+            TerrainPreset::Islands => "islands",
+            TerrainPreset::Caves => "caves",
+            TerrainPreset::Floating => "floating",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        TerrainPreset::ALL
+            .iter()
+            .copied()
+            .find(|p| p.name() == name)
+    }
+
+    pub fn settings(&self) -> TerrainSettings {
+        match self {
+            TerrainPreset::Hills => TerrainSettings::default(),
+            TerrainPreset::Islands => TerrainSettings {
+                ground_level: 0.82,
+                hill_height: 0.4,
+                hill_count: 5.0,
+                island_edges: true,
+                water_level: Some(0.82),
+                ..TerrainSettings::default()
+            },
+            TerrainPreset::Caves => TerrainSettings {
+                ground_level: 0.45,
+                hill_height: 0.15,
+                cave_density: 0.45,
+                platforms: 0,
+                bedrock_rows: 2,
+                ..TerrainSettings::default()
+            },
+            TerrainPreset::Floating => TerrainSettings {
+                ground_level: 0.95,
+                hill_height: 0.05,
+                platforms: 8,
+                water_level: Some(0.9),
+                ..TerrainSettings::default()
+            },
+        }
+    }
+}
+
+/// Усі розміри — частки ширини чи висоти карти, щоб рельєф не ламався на
+/// малих полотнах.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TerrainSettings {
+    pub ground_level: f64, // середній рівень землі, частка висоти згори
+    pub hill_height: f64,  // розмах горбів, частка висоти
+    pub hill_count: f64,   // скільки великих горбів уміщається по ширині
+    pub cave_density: f64, // 0 — без печер, 1 — сир
+    pub platforms: usize,
+    pub island_edges: bool,  // краї карти йдуть під воду
+    pub bedrock_rows: usize, // нижні рядки з незруйновного каменю
+    pub water_level: Option<f64>,
+}
+
+impl Default for TerrainSettings {
+    fn default() -> Self {
+        Self {
+            ground_level: 0.7,
+            hill_height: 0.2,
+            hill_count: 3.0,
+            cave_density: 0.0,
+            platforms: 2,
+            island_edges: false,
+            bedrock_rows: 0,
+            water_level: Some(0.95),
+        }
+    }
+}
+
+pub struct Terrain {
+    pub tiles: Vec<Vec<Material>>,
+    pub water_row: Option<usize>,
+}
+
+pub fn generate(seed: u64, cols: usize, rows: usize, settings: &TerrainSettings) -> Terrain {
+    let mut tiles = vec![vec![Material::Air; cols]; rows];
+    if cols == 0 || rows == 0 {
+        return Terrain {
+            tiles,
+            water_row: None,
+        };
+    }
+
+    let rows_f = rows as f64;
+    let cols_f = cols as f64;
+    let mut surface = vec![rows; cols];
+
+    for (col, top) in surface.iter_mut().enumerate() {
+        let x = col as f64 / cols_f * settings.hill_count;
+        let mut height =
+            settings.ground_level - (fbm_1d(seed, x) - 0.5) * 2.0 * settings.hill_height;
+        if settings.island_edges {
+            // до країв земля плавно опускається під воду
+            let edge = (col.min(cols - 1 - col) as f64 / (cols_f * 0.15)).min(1.0);
+            height += (1.0 - smoothstep(edge)) * 0.3;
+        }
+        let row = (height * rows_f).round().clamp(0.0, rows_f) as usize;
+        *top = row;
+        for tiles_row in tiles.iter_mut().skip(row) {
+            tiles_row[col] = Material::Dirt;
+        }
+    }
+
+    if settings.cave_density > 0.0 {
+        let threshold = 1.0 - settings.cave_density * 0.6;
+        let scale = 12.0 / cols_f.max(rows_f);
+        for (row, tiles_row) in tiles.iter_mut().enumerate() {
+            for (col, tile) in tiles_row.iter_mut().enumerate() {
+                // кірку над печерами не чіпаємо, щоб поверхня лишалась цілою
+                if row < surface[col] + 4 || row + 2 >= rows {
+                    continue;
+                }
+                let n = value_noise_2d(seed ^ 0xCA7E, col as f64 * scale, row as f64 * scale);
+                if n > threshold {
+                    *tile = Material::Air;
+                }
+            }
+        }
+    }
+
+    let mut rng = TerrainRng::new(seed ^ 0x9147_F0A7);
+    for _ in 0..settings.platforms {
+        let width = ((0.05 + rng.next_f64() * 0.07) * cols_f).max(3.0) as usize;
+        let thickness = ((rows_f * 0.03).round() as usize).max(1);
+        let col_start = (rng.next_f64() * cols_f) as usize;
+        let row =
+            ((0.2 + rng.next_f64() * (settings.ground_level - 0.3).max(0.0)) * rows_f) as usize;
+        for tiles_row in tiles.iter_mut().skip(row).take(thickness) {
+            let col_end = (col_start + width).min(cols);
+            tiles_row[col_start.min(col_end)..col_end].fill(Material::Dirt);
+        }
+    }
+
+    for tiles_row in tiles.iter_mut().rev().take(settings.bedrock_rows) {
+        tiles_row.fill(Material::Rock);
+    }
+
+    Terrain {
+        tiles,
+        water_row: settings
+            .water_level
+            .map(|level| ((level * rows_f).round() as usize).min(rows)),
+    }
+}
+
+/// splitmix64 — маленький детермінований генератор.
+struct TerrainRng(u64);
+
+impl TerrainRng {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        mix(self.0)
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Псевдовипадкове число 0.0..1.0 для вузла ґратки.
+fn lattice(seed: u64, x: i64, y: i64) -> f64 {
+    let h = mix(seed ^ mix((x as u64) ^ mix(y as u64)));
+    (h >> 11) as f64 / (1u64 << 53) as f64
+}
+
+fn smoothstep(t: f64) -> f64 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+fn value_noise_1d(seed: u64, x: f64) -> f64 {
+    let x0 = x.floor();
+    let t = smoothstep(x - x0);
+    let a = lattice(seed, x0 as i64, 0);
+    let b = lattice(seed, x0 as i64 + 1, 0);
+    a + (b - a) * t
+}
+
+fn value_noise_2d(seed: u64, x: f64, y: f64) -> f64 {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (tx, ty) = (smoothstep(x - x0), smoothstep(y - y0));
+    let (ix, iy) = (x0 as i64, y0 as i64);
+    let top = lattice(seed, ix, iy) + (lattice(seed, ix + 1, iy) - lattice(seed, ix, iy)) * tx;
+    let bottom = lattice(seed, ix, iy + 1)
+        + (lattice(seed, ix + 1, iy + 1) - lattice(seed, ix, iy + 1)) * tx;
+    top + (bottom - top) * ty
+}
+
+/// Кілька октав шуму: великі горби плюс дрібні нерівності. Результат 0.0..1.0.
+fn fbm_1d(seed: u64, x: f64) -> f64 {
+    let mut total = 0.0;
+    let mut amplitude = 0.5;
+    let mut frequency = 1.0;
+    let mut norm = 0.0;
+    for octave in 0..4u64 {
+        total += value_noise_1d(seed.wrapping_add(octave), x * frequency) * amplitude;
+        norm += amplitude;
+        amplitude *= 0.5;
+        frequency *= 2.0;
+    }
+    total / norm
+}
@@ -0,0 +1,69 @@
+use crate::animation::Animation;
+use crate::models::body::PhysicsBody;
+use crate::models::map::Map;
+use crate::render::Renderer;
+
+pub trait CanvasObject {
+    fn draw(&self, renderer: &dyn Renderer);
+    /// Власна поведінка за крок. Тіла [`Entity`] фізику отримують від `Game`,
+    /// тож їм це потрібно лише для чогось понад неї.
+    fn update(&mut self, _delta_time: f64, _map: &Map, _canvas_height: f64) {}
+}
+
+/// Сутність з фізичним тілом. Цього досить, щоб отримати `GravityObject`
+/// і `MovableObject` — реалізації нижче спільні для всіх.
+pub trait HasBody {
+    fn body(&self) -> &PhysicsBody;
+    fn body_mut(&mut self) -> &mut PhysicsBody;
+}
+
+/// Об'єкт світу з тілом. `Game` сам рахує для нього гравітацію, зіткнення
+/// й поштовхи від вибухів.
+pub trait Entity: CanvasObject + HasBody {}
+
+impl<T: CanvasObject + HasBody> Entity for T {}
+
+pub trait GravityObject: CanvasObject {
+    fn apply_gravity(&mut self, delta_time: f64, map: &Map, world_height: f64);
+    fn is_on_ground(&self, map: &Map) -> bool;
+}
+
+pub trait MovableObject: CanvasObject {
+    fn change_position(&mut self, dx: f64, dy: f64);
+    fn apply_impulse(&mut self, dx: f64, dy: f64);
+    fn try_move_y(&mut self, dy: f64, map: &Map, world_height: f64) -> bool;
+    fn try_move_x(&mut self, dx: f64, map: &Map) -> bool;
+}
+
+pub trait AnimatedObject: CanvasObject {
+    fn set_animation(&mut self, animation: Animation);
+    fn update_animation_state(&mut self, is_moving: bool, is_on_ground: bool);
+}
+
+impl<T: HasBody + CanvasObject> GravityObject for T {
+    fn apply_gravity(&mut self, delta_time: f64, map: &Map, world_height: f64) {
+        self.body_mut().apply_gravity(delta_time, map, world_height);
+    }
+
+    fn is_on_ground(&self, map: &Map) -> bool {
+        self.body().is_on_ground(map)
+    }
+}
+
+impl<T: HasBody + CanvasObject> MovableObject for T {
+    fn change_position(&mut self, dx: f64, dy: f64) {
+        self.body_mut().position.move_by(dx, dy);
+    }
+
+    fn apply_impulse(&mut self, dx: f64, dy: f64) {
+        self.body_mut().apply_impulse(dx, dy);
+    }
+
+    fn try_move_y(&mut self, dy: f64, map: &Map, world_height: f64) -> bool {
+        self.body_mut().try_move_y(dy, map, world_height)
+    }
+
+    fn try_move_x(&mut self, dx: f64, map: &Map) -> bool {
+        self.body_mut().try_move_x(dx, map)
+    }
+}
@@ -0,0 +1,248 @@
+use std::fmt;
+
+use crate::assets::{character_key, CHARACTER_COLORS};
+
+pub const DEFAULT_TURN_TIME: f64 = 45.0;
+pub const DEFAULT_RETREAT_TIME: f64 = 3.0;
+
+pub struct Team {
+    pub name: String,
+    pub color: String,       // один з CHARACTER_COLORS, у матчі не повторюється
+    pub members: Vec<usize>, // індекси в Game::players
+    next_member: usize,
+}
+
+impl Team {
+    /// Ключ спрайтшита, яким малюються черв'яки команди.
+    pub fn sprite(&self) -> String {
+        character_key(&self.color)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TeamError {
+    UnknownColor(String),
+    ColorTaken(String),
+    UnknownTeam(usize),
+}
+
+impl fmt::Display for TeamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TeamError::UnknownColor(color) => write!(f, "unknown team colour '{}'", color),
+            TeamError::ColorTaken(color) => write!(f, "colour '{}' is already taken", color),
+            TeamError::UnknownTeam(team) => write!(f, "no team with index {}", team),
+        }
+    }
+}
+
+impl std::error::Error for TeamError {}
+
+/// Чи можна взяти `color`, коли зайняті кольори `taken`.
+pub fn check_color<'a>(
+    color: &str,
+    mut taken: impl Iterator<Item = &'a str>,
+) -> Result<(), TeamError> {
+    if !CHARACTER_COLORS.contains(&color) {
+        return Err(TeamError::UnknownColor(color.to_string()));
+    }
+    if taken.any(|t| t == color) {
+        return Err(TeamError::ColorTaken(color.to_string()));
+    }
+    Ok(())
+}
+
+/// Перший колір, якого немає серед `taken`.
+pub fn free_color<'a>(taken: impl Iterator<Item = &'a str> + Clone) -> Option<&'static str> {
+    CHARACTER_COLORS
+        .iter()
+        .copied()
+        .find(|color| check_color(color, taken.clone()).is_ok())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TurnPhase {
+    Playing,
+    Retreat,
+}
+
+pub struct TurnManager {
+    pub teams: Vec<Team>,
+    pub turn_time: f64,
+    pub retreat_time: f64,
+    current_team: usize,
+    current_player: Option<usize>,
+    remaining: f64,
+    phase: TurnPhase,
+}
+
+impl TurnManager {
+    pub fn new(turn_time: f64, retreat_time: f64) -> Self {
+        Self {
+            teams: Vec::new(),
+            turn_time,
+            retreat_time,
+            current_team: 0,
+            current_player: None,
+            remaining: turn_time,
+            phase: TurnPhase::Playing,
+        }
+    }
+
+    /// Команда з першим вільним кольором. Коли всі кольори зайняті (команд
+    /// більше, ніж спрайтшитів), кольори йдуть по колу.
+    pub fn add_team(&mut self, name: &str) -> usize {
+        let color = free_color(self.colors())
+            .unwrap_or(CHARACTER_COLORS[self.teams.len() % CHARACTER_COLORS.len()]);
+        self.push_team(name, color)
+    }
+
+    /// Команда з вибраним кольором; колір має бути вільним.
+    pub fn add_team_with_color(&mut self, name: &str, color: &str) -> Result<usize, TeamError> {
+        check_color(color, self.colors())?;
+        Ok(self.push_team(name, color))
+    }
+
+    fn push_team(&mut self, name: &str, color: &str) -> usize {
+        self.teams.push(Team {
+            name: name.to_string(),
+            color: color.to_string(),
+            members: Vec::new(),
+            next_member: 0,
+        });
+        self.teams.len() - 1
+    }
+
+    fn colors(&self) -> impl Iterator<Item = &str> + Clone {
+        self.teams.iter().map(|t| t.color.as_str())
+    }
+
+    /// Дає команді `team` колір `color`. Якщо ним уже грає інша команда, вона
+    /// забирає старий колір `team` — кольори лишаються унікальними. Повертає
+    /// індекс команди, якій довелося змінити колір.
+    pub fn set_team_color(&mut self, team: usize, color: &str) -> Result<Option<usize>, TeamError> {
+        check_color(color, std::iter::empty())?;
+        if team >= self.teams.len() {
+            return Err(TeamError::UnknownTeam(team));
+        }
+        let old = std::mem::replace(&mut self.teams[team].color, color.to_string());
+        let other = (0..self.teams.len()).find(|&t| t != team && self.teams[t].color == color);
+        if let Some(other) = other {
+            self.teams[other].color = old;
+        }
+        Ok(other)
+    }
+
+    pub fn add_member(&mut self, team: usize, player_index: usize) {
+        let Some(t) = self.teams.get_mut(team) else {
+            return;
+        };
+        t.members.push(player_index);
+
+        // перший черв'як у матчі одразу отримує хід
+        if self.current_player.is_none() {
+            self.current_team = team;
+            self.start_turn_for(team);
+        }
+    }
+
+    /// Прибирає черв'яка з черги (напр. загиблого). Повертає `false`, якщо його там не було.
+    /// Якщо це був поточний гравець, хід лишається за ним, поки не викличуть `next_turn`.
+    pub fn remove_member(&mut self, player_index: usize) -> bool {
+        for team in &mut self.teams {
+            let Some(pos) = team.members.iter().position(|&m| m == player_index) else {
+                continue;
+            };
+            team.members.remove(pos);
+            if pos < team.next_member {
+                team.next_member -= 1;
+            }
+            if team.next_member >= team.members.len() {
+                team.next_member = 0;
+            }
+            return true;
+        }
+        false
+    }
+
+    pub fn current_player(&self) -> Option<usize> {
+        self.current_player
+    }
+
+    pub fn current_team(&self) -> Option<usize> {
+        self.current_player.map(|_| self.current_team)
+    }
+
+    pub fn seconds_left(&self) -> f64 {
+        self.remaining.max(0.0)
+    }
+
+    pub fn phase(&self) -> TurnPhase {
+        self.phase
+    }
+
+    pub fn can_fire(&self) -> bool {
+        self.current_player.is_some() && self.phase == TurnPhase::Playing
+    }
+
+    /// Після пострілу лишається трохи часу, щоб відбігти, і хід закінчується.
+    pub fn on_fired(&mut self) {
+        self.phase = TurnPhase::Retreat;
+        self.remaining = self.retreat_time;
+    }
+
+    /// Відлік таймера. `busy` — у повітрі ще є снаряди, тож хід не передаємо.
+    /// Повертає `true`, коли час ходу вийшов і пора передавати хід.
+    pub fn update(&mut self, delta_time: f64, busy: bool) -> bool {
+        if self.current_player.is_none() {
+            return false;
+        }
+
+        self.remaining -= delta_time;
+        self.remaining <= 0.0 && !busy
+    }
+
+    pub fn next_turn(&mut self) {
+        let team_count = self.teams.len();
+        for offset in 1..=team_count {
+            let team = (self.current_team + offset) % team_count;
+            if !self.teams[team].members.is_empty() {
+                self.current_team = team;
+                self.start_turn_for(team);
+                return;
+            }
+        }
+        self.current_player = None;
+    }
+
+    /// Примусово віддає хід конкретному черв'якові (напр. за повідомленням від сервера).
+    pub fn force_turn(&mut self, team: usize, player: usize) {
+        let Some(t) = self.teams.get_mut(team) else {
+            return;
+        };
+        if let Some(pos) = t.members.iter().position(|&m| m == player) {
+            t.next_member = (pos + 1) % t.members.len();
+        }
+
+        self.current_team = team;
+        self.current_player = Some(player);
+        self.remaining = self.turn_time;
+        self.phase = TurnPhase::Playing;
+    }
+
+    fn start_turn_for(&mut self, team: usize) {
+        let t = &mut self.teams[team];
+        let member = t.members[t.next_member % t.members.len()];
+        t.next_member = (t.next_member + 1) % t.members.len();
+
+        self.current_player = Some(member);
+        self.remaining = self.turn_time;
+        self.phase = TurnPhase::Playing;
+    }
+}
+
+impl Default for TurnManager {
+    fn default() -> Self {
+        Self::new(DEFAULT_TURN_TIME, DEFAULT_RETREAT_TIME)
+    }
+}
@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+
+use crate::input::Action;
+
+/// Піднімаємо щоразу, коли змінюється формат повідомлень.
+pub const PROTOCOL_VERSION: u32 = 7;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Envelope {
+    pub version: u32,
+    pub message: Message,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+    /// Клієнт -> сервер. Сервер пересилає іншим із заповненими `client_id` і `slot`.
+    /// `name` — назва команди, `color` — бажаний колір; сервер пересилає вже
+    /// призначений колір, унікальний у лобі.
+    Join {
+        #[serde(default)]
+        client_id: u32,
+        #[serde(default)]
+        slot: usize,
+        name: String,
+        #[serde(default)]
+        color: Option<String>,
+    },
+    /// Сервер -> клієнт у відповідь на `Join`. `slot` — індекс команди цього клієнта,
+    /// `color` — її колір, `seed` і `preset` — з чого всі клієнти генерують ту саму карту.
+    Welcome {
+        client_id: u32,
+        slot: usize,
+        color: String,
+        seed: u64,
+        preset: String,
+    },
+    Input {
+        #[serde(default)]
+        client_id: u32,
+        actions: Vec<Action>,
+        #[serde(default)]
+        fire: Option<FireInput>,
+    },
+    Snapshot {
+        tick: u64,
+        players: Vec<PlayerState>,
+    },
+    TurnChange {
+        team: usize,
+        player: usize,
+        seconds_left: f64,
+    },
+    Chat {
+        #[serde(default)]
+        client_id: u32,
+        text: String,
+    },
+    Leave {
+        #[serde(default)]
+        client_id: u32,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FireInput {
+    pub weapon: String,
+    pub angle: f64, // радіани, як у Game::fire
+    pub power: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlayerState {
+    pub x: f64,
+    pub y: f64,
+    pub velocity_x: f64, // px/s
+    pub velocity_y: f64,
+    pub facing_left: bool,
+    pub health: f64,
+}
+
+#[derive(Debug)]
+pub enum ProtocolError {
+    Malformed(String),
+    VersionMismatch { expected: u32, got: u32 },
+}
+
+impl std::fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProtocolError::Malformed(reason) => write!(f, "malformed message: {}", reason),
+            ProtocolError::VersionMismatch { expected, got } => write!(
+                f,
+                "protocol version mismatch: expected {}, got {}",
+                expected, got
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ProtocolError {}
+
+pub fn encode(message: Message) -> String {
+    let envelope = Envelope {
+        version: PROTOCOL_VERSION,
+        message,
+    };
+    serde_json::to_string(&envelope).expect("protocol messages are always serializable")
+}
+
+pub fn decode(text: &str) -> Result<Message, ProtocolError> {
+    let envelope: Envelope =
+        serde_json::from_str(text).map_err(|e| ProtocolError::Malformed(e.to_string()))?;
+    if envelope.version != PROTOCOL_VERSION {
+        return Err(ProtocolError::VersionMismatch {
+            expected: PROTOCOL_VERSION,
+            got: envelope.version,
+        });
+    }
+    Ok(envelope.message)
+}
@@ -0,0 +1,67 @@
+/// Мінімальний набір операцій малювання, який потрібен грі.
+/// У браузері його реалізує обгортка над `CanvasRenderingContext2d`.
+pub trait Renderer {
+    fn clear(&self, width: f64, height: f64);
+    fn fill_rect(&self, rect: Rect, color: &str);
+    fn fill_circle(&self, x: f64, y: f64, radius: f64, color: &str);
+    /// Малює кадр `source` зі спрайтшита `sprite` (ключ, під яким зображення
+    /// зареєстроване в рендерері) у прямокутник `dest`; `flip_x` дзеркалить кадр.
+    fn draw_sprite(&self, sprite: &str, source: Rect, dest: Rect, flip_x: bool);
+
+    /// Усе, що малюється до [`Renderer::pop_transform`], зсувається на
+    /// `(-offset_x, -offset_y)` і масштабується в `scale` разів — так камера
+    /// показує частину світу.
+    fn push_transform(&self, _offset_x: f64, _offset_y: f64, _scale: f64) {}
+    fn pop_transform(&self) {}
+
+    /// Перемикає малювання в закешований позаекранний шар `layer` розміром
+    /// `width` x `height`, доки не викличуть [`Renderer::end_layer`].
+    /// Рендерери без шарів (типово) повертають [`LayerState::Unsupported`] —
+    /// тоді все малюється напряму щокадру.
+    fn begin_layer(&self, _layer: &str, _width: f64, _height: f64) -> LayerState {
+        LayerState::Unsupported
+    }
+    fn end_layer(&self) {}
+    /// Стирає прямокутник поточного шару до прозорості.
+    fn clear_rect(&self, _rect: Rect) {}
+    /// Виводить готовий шар на екран.
+    fn draw_layer(&self, _layer: &str) {}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LayerState {
+    Unsupported,
+    /// Шар щойно створено (або змінився розмір) — його треба намалювати повністю.
+    Fresh,
+    /// У шарі лишилось те, що намалювали минулого разу.
+    Cached,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Rect {
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+/// Рендерер, що нічого не малює — для тестів і серверної симуляції.
+pub struct NullRenderer;
+
+impl Renderer for NullRenderer {
+    fn clear(&self, _width: f64, _height: f64) {}
+    fn fill_rect(&self, _rect: Rect, _color: &str) {}
+    fn fill_circle(&self, _x: f64, _y: f64, _radius: f64, _color: &str) {}
+    fn draw_sprite(&self, _sprite: &str, _source: Rect, _dest: Rect, _flip_x: bool) {}
+}
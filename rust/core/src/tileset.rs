@@ -0,0 +1,129 @@
+//! Текстури землі з аркуша `tile_jungle_ground_brown.png`.
+//!
+//! Аркуш — готова сцена, а не сітка тайлів, тож беремо з нього смуги: профіль
+//! ґрунту (трава -> коріння -> темна глибина), краї кам'яного блоку, низ
+//! платформи і закруглені кінці куща для кутів трави. Тайл карти малює
+//! квадрат `TEXELS_PER_TILE` з потрібної смуги, зсунутий за його колонкою,
+//! тож сусідні тайли складаються в суцільну текстуру.
+
+use crate::assets::JUNGLE_TILES;
+use crate::models::map::Map;
+use crate::models::material::Material;
+use crate::render::{Rect, Renderer};
+
+pub const TEXELS_PER_TILE: f64 = 10.0;
+pub const MAX_DEPTH: usize = 32; // глибше різниці в текстурі вже немає
+
+/// Яку частину аркуша бере тайл залежно від сусідів.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TileKind {
+    Top,
+    TopLeft,
+    TopRight,
+    Left,
+    Right,
+    Bottom,
+    /// Внутрішній тайл; `depth` — скільки твердих тайлів над ним.
+    Interior {
+        depth: usize,
+    },
+}
+
+pub struct TileSet {
+    pub sprite: String,
+    /// Вертикальний профіль: трава й коріння згори, далі глибина, що повторюється.
+    pub profile: Rect,
+    pub deep: Rect,
+    pub top_left: Rect,
+    pub top_right: Rect,
+    pub left: Rect,
+    pub right: Rect,
+    pub bottom: Rect,
+}
+
+impl TileSet {
+    pub fn jungle() -> Self {
+        Self {
+            sprite: JUNGLE_TILES.to_string(),
+            profile: Rect::new(0.0, 930.0, 1900.0, 100.0),
+            deep: Rect::new(0.0, 1030.0, 1900.0, 40.0),
+            top_left: Rect::new(1516.0, 876.0, 10.0, 10.0),
+            top_right: Rect::new(1828.0, 876.0, 10.0, 10.0),
+            left: Rect::new(742.0, 760.0, 10.0, 160.0),
+            right: Rect::new(929.0, 760.0, 10.0, 160.0),
+            bottom: Rect::new(980.0, 631.0, 340.0, 10.0),
+        }
+    }
+
+    /// Квадрат аркуша для тайла `kind` у колонці `col` і рядку `row`.
+    pub fn source_rect(&self, kind: TileKind, col: usize, row: usize) -> Rect {
+        let texel = |region: Rect, col: usize, row: usize| {
+            let x = (col as f64 * TEXELS_PER_TILE) % (region.width - TEXELS_PER_TILE + 1.0);
+            let y = (row as f64 * TEXELS_PER_TILE) % (region.height - TEXELS_PER_TILE + 1.0);
+            Rect::new(region.x + x, region.y + y, TEXELS_PER_TILE, TEXELS_PER_TILE)
+        };
+        let profile_rows = (self.profile.height / TEXELS_PER_TILE) as usize;
+        match kind {
+            TileKind::Top => texel(self.profile, col, 0),
+            TileKind::Interior { depth } if depth < profile_rows => texel(self.profile, col, depth),
+            TileKind::Interior { depth } => texel(self.deep, col, depth - profile_rows),
+            TileKind::TopLeft => texel(self.top_left, 0, 0),
+            TileKind::TopRight => texel(self.top_right, 0, 0),
+            TileKind::Left => texel(self.left, 0, row),
+            TileKind::Right => texel(self.right, 0, row),
+            TileKind::Bottom => texel(self.bottom, col, 0),
+        }
+    }
+
+    /// Малює один тайл землі в прямокутник `dest`.
+    pub fn draw_tile(
+        &self,
+        renderer: &dyn Renderer,
+        map: &Map,
+        col: usize,
+        row: usize,
+        dest: Rect,
+    ) {
+        let source = self.source_rect(tile_kind(map, col, row), col, row);
+        renderer.draw_sprite(&self.sprite, source, dest, false);
+    }
+
+    /// Які матеріали малюються текстурою, а не заливкою.
+    pub fn textures(&self, material: Material) -> bool {
+        material == Material::Dirt
+    }
+}
+
+/// Автотайлінг: вибір шматка аркуша за тим, де в тайла повітря.
+pub fn tile_kind(map: &Map, col: usize, row: usize) -> TileKind {
+    let (c, r) = (col as isize, row as isize);
+    // за боковими й нижнім краєм карти вважаємо землю, щоб там не було "обриву"
+    let open = |dc: isize, dr: isize| {
+        let (col, row) = (c + dc, r + dr);
+        if row < 0 {
+            return true;
+        }
+        let outside = col < 0 || col >= map.cols() as isize || row >= map.rows() as isize;
+        !outside && !map.tile(col, row).is_solid()
+    };
+    let (up, down, left, right) = (open(0, -1), open(0, 1), open(-1, 0), open(1, 0));
+
+    if up {
+        match (left, right) {
+            (true, false) => TileKind::TopLeft,
+            (false, true) => TileKind::TopRight,
+            _ => TileKind::Top,
+        }
+    } else if down {
+        TileKind::Bottom
+    } else if left {
+        TileKind::Left
+    } else if right {
+        TileKind::Right
+    } else {
+        let depth = (1..MAX_DEPTH)
+            .find(|d| !map.tile(c, r - *d as isize).is_solid())
+            .map_or(MAX_DEPTH, |d| d - 1);
+        TileKind::Interior { depth }
+    }
+}
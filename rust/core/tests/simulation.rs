@@ -0,0 +1,1399 @@
+//! Нативні тести симуляції — без браузера і без canvas.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use wararar_core::animation::{Animation, AnimationError, AnimationSet};
+use wararar_core::assets::{self, Asset, AssetCache, AssetError, AssetKind, AssetManifest};
+use wararar_core::camera::Camera;
+use wararar_core::gamepad::{GamepadAssignments, GamepadMapping};
+use wararar_core::input::{Action, InputState, KeyBindings};
+use wararar_core::models::body::PhysicsBody;
+use wararar_core::models::event::GameEvent;
+use wararar_core::models::game::{Game, DEFAULT_FIRE_POWER, FIXED_TIMESTEP};
+use wararar_core::models::map::{Map, SpawnPoint};
+use wararar_core::models::map_file::{self, MapError, MapFile};
+use wararar_core::models::material::Material;
+use wararar_core::models::player::{self, FallDamage, Player, MAX_HEALTH, SPAWN_TIME, WALK_SPEED};
+use wararar_core::models::position::Position;
+use wararar_core::models::projectile::{Projectile, WeaponKind};
+use wararar_core::models::terrain::TerrainPreset;
+use wararar_core::models::traits::{CanvasObject, GravityObject, HasBody, MovableObject};
+use wararar_core::models::turn::{TeamError, TurnPhase};
+use wararar_core::protocol::{self, FireInput, Message, ProtocolError};
+use wararar_core::render::{LayerState, NullRenderer, Rect, Renderer};
+use wararar_core::tileset::{self, TileKind, TileSet};
+
+const TILE: f64 = 5.0;
+
+/// 40x40 тайлів, нижні 5 рядків — суцільна земля.
+fn flat_map() -> Map {
+    let mut data = vec![vec![Material::Air; 40]; 40];
+    for row in data.iter_mut().skip(35) {
+        row.fill(Material::Dirt);
+    }
+    Map::from_tiles(TILE, data)
+}
+
+fn small_player(x: f64, y: f64) -> Player {
+    let mut player = Player::new(Position::new(x, y), None);
+    player.body.width = 10.0;
+    player.body.height = 10.0;
+    player
+}
+
+fn keys(list: &[&str]) -> HashSet<Action> {
+    KeyBindings::default().actions(list.iter().copied())
+}
+
+#[test]
+fn carve_circle_removes_tiles_and_marks_dirty() {
+    let mut map = flat_map();
+    assert!(map.is_solid_at(100.0, 180.0));
+
+    let removed = map.carve_circle(100.0, 180.0, 12.0);
+
+    assert!(removed > 0);
+    assert!(!map.is_solid_at(100.0, 180.0));
+    assert!(map.is_solid_at(10.0, 180.0));
+    let dirty = map.take_dirty().expect("carving marks a dirty region");
+    assert!(dirty.col_start <= 20 && dirty.col_end > 20);
+    assert!(map.take_dirty().is_none());
+}
+
+#[test]
+fn game_explosion_carves_a_crater_and_reports_it() {
+    let mut game = Game::new(200.0, 200.0);
+    game.set_map(flat_map());
+
+    game.explode(100.0, 180.0, 12.0);
+
+    assert!(!game.map.is_solid_at(100.0, 180.0));
+    let events = game.take_events();
+    assert!(events.iter().any(|e| matches!(
+        e,
+        GameEvent::Explosion { tiles_removed, .. } if *tiles_removed > 0
+    )));
+}
+
+#[test]
+fn same_seed_generates_identical_map() {
+    for preset in TerrainPreset::ALL {
+        let a = Map::generate(800.0, 600.0, 42, preset);
+        let b = Map::generate(800.0, 600.0, 42, preset);
+        let c = Map::generate(800.0, 600.0, 43, preset);
+        assert_eq!(a.data, b.data, "{}", preset.name());
+        assert_ne!(a.data, c.data, "{}", preset.name());
+        assert_eq!(a.water_level, b.water_level);
+    }
+}
+
+#[test]
+fn generator_handles_small_canvases() {
+    for preset in TerrainPreset::ALL {
+        for (width, height) in [(0.0, 0.0), (12.0, 7.0), (120.0, 80.0)] {
+            let map = Map::generate(width, height, 7, preset);
+            assert_eq!(map.cols(), (width / map.tile_size) as usize);
+            assert_eq!(map.rows(), (height / map.tile_size) as usize);
+        }
+    }
+    assert_eq!(
+        TerrainPreset::from_name("caves"),
+        Some(TerrainPreset::Caves)
+    );
+}
+
+#[test]
+fn map_file_round_trips_cratered_map() {
+    let mut map = flat_map();
+    map.carve_circle(100.0, 180.0, 12.0);
+    map.spawn_points.push(SpawnPoint {
+        x: 30.0,
+        y: 175.0,
+        team: Some(1),
+    });
+
+    let text = MapFile::from_map(&map, "test").to_json();
+    let loaded = MapFile::parse(&text).unwrap().into_map().unwrap();
+
+    assert_eq!(loaded.data, map.data);
+    assert_eq!(loaded.spawn_points, map.spawn_points);
+    assert_eq!(loaded.tile_size, TILE);
+}
+
+#[test]
+fn map_file_reports_bad_tiles() {
+    let text = r#"{"version": 2, "tile_size": 5, "tiles": ["..#", ".x#"]}"#;
+    let result = MapFile::parse(text).unwrap().into_map();
+    assert!(matches!(
+        result,
+        Err(MapError::UnknownTile {
+            row: 1,
+            symbol: 'x'
+        })
+    ));
+    assert!(matches!(
+        MapFile::parse(r#"{"version": 9, "tile_size": 5, "tiles": []}"#),
+        Err(MapError::UnsupportedVersion(9))
+    ));
+}
+
+#[test]
+fn map_file_rejects_bad_tile_size() {
+    for size in ["0", "-5"] {
+        let text = format!(
+            r#"{{"version": 2, "tile_size": {}, "tiles": ["..#"]}}"#,
+            size
+        );
+        let result = MapFile::parse(&text).unwrap().into_map();
+        assert!(matches!(result, Err(MapError::BadTileSize(_))), "{}", size);
+    }
+    let rgba = [0, 0, 0, 0];
+    for size in [0.0, f64::NAN, f64::INFINITY] {
+        assert!(matches!(
+            map_file::from_alpha_mask(1, 1, &rgba, size),
+            Err(MapError::BadTileSize(_))
+        ));
+    }
+}
+
+#[test]
+fn version_1_map_files_load_as_dirt_and_air() {
+    let text = r##"{
+        "version": 1,
+        "tile_size": 5,
+        "legend": { ".": 0, "#": 1, "x": 7 },
+        "tiles": ["..#", "x##"]
+    }"##;
+    let map = MapFile::parse(text).unwrap().into_map().unwrap();
+    assert_eq!(
+        map.data,
+        vec![
+            vec![Material::Air, Material::Air, Material::Dirt],
+            vec![Material::Dirt; 3]
+        ]
+    );
+
+    // без legend діють символи за замовчуванням
+    let text = r#"{"version": 1, "tile_size": 5, "tiles": [".#"]}"#;
+    let map = MapFile::parse(text).unwrap().into_map().unwrap();
+    assert_eq!(map.data, vec![vec![Material::Air, Material::Dirt]]);
+}
+
+#[test]
+fn alpha_mask_builds_tiles_and_exports_back() {
+    // 2x2: прозорий, непрозорий / напівпрозорий, сірий (камінь)
+    let rgba = [0, 0, 0, 0, 9, 9, 9, 255, 9, 9, 9, 100, 128, 128, 128, 200];
+    let map = map_file::from_alpha_mask(2, 2, &rgba, TILE).unwrap();
+    assert_eq!(
+        map.data,
+        vec![
+            vec![Material::Air, Material::Dirt],
+            vec![Material::Air, Material::Rock]
+        ]
+    );
+
+    let exported = map_file::to_alpha_mask(&map);
+    let reloaded = map_file::from_alpha_mask(2, 2, &exported, TILE).unwrap();
+    assert_eq!(reloaded.data, map.data);
+    assert!(map_file::from_alpha_mask(3, 2, &rgba, TILE).is_err());
+}
+
+#[test]
+fn players_are_placed_on_their_team_spawns() {
+    let mut game = Game::new(800.0, 600.0);
+    game.map = flat_map();
+    game.map.spawn_points = vec![
+        SpawnPoint {
+            x: 150.0,
+            y: 175.0,
+            team: Some(1),
+        },
+        SpawnPoint {
+            x: 40.0,
+            y: 175.0,
+            team: None,
+        },
+    ];
+    game.add_player(small_player(0.0, 0.0));
+    game.add_player(small_player(0.0, 0.0));
+
+    game.place_players_at_spawns();
+
+    assert_eq!(game.players[0].body.position, Position::new(35.0, 165.0));
+    assert_eq!(game.players[1].body.position, Position::new(145.0, 165.0));
+}
+
+#[test]
+fn spawns_are_safe_and_spread_by_team() {
+    let mut game = Game::new(200.0, 200.0);
+    let mut map = flat_map();
+    // колона посередині і лава праворуч — там стояти не можна
+    for row in 10..35 {
+        map.data[row][20] = Material::Rock;
+    }
+    for col in 30..40 {
+        map.data[34][col] = Material::Lava;
+    }
+    game.set_map(map);
+    let first = game.add_team("A");
+    let second = game.add_team("B");
+    for team in [first, first, second, second] {
+        game.add_player_to_team(team, small_player(0.0, 0.0));
+    }
+
+    game.place_players_at_spawns();
+
+    let positions: Vec<Position> = game.players.iter().map(|p| p.body.position).collect();
+    for (i, p) in positions.iter().enumerate() {
+        assert!(game.map.can_move_to(p.x, p.y, 10.0, 10.0), "{:?}", p);
+        assert!(game.players[i].is_on_ground(&game.map), "{:?}", p);
+        assert!(p.x + 10.0 <= 30.0 * TILE, "in lava: {:?}", p);
+        for q in &positions[i + 1..] {
+            assert!((p.x - q.x).abs() >= 10.0);
+        }
+    }
+    assert!(positions[0].x < 100.0 && positions[1].x < 100.0);
+    assert!(positions[2].x >= 100.0 && positions[3].x >= 100.0);
+}
+
+#[test]
+fn spawn_animation_plays_before_control() {
+    let mut game = Game::new(200.0, 200.0);
+    game.set_map(flat_map());
+    let animation = player::character_animation("worm.png");
+    let mut worm = Player::new(Position::new(0.0, 0.0), Some(animation));
+    worm.body.width = 10.0;
+    worm.body.height = 10.0;
+    game.add_player(worm);
+    game.place_players_at_spawns();
+    let start_x = game.players[0].body.position.x;
+
+    assert!(game.players[0].is_spawning());
+    let animation = game.players[0].animation.as_ref().unwrap();
+    assert_eq!(animation.animation_row(), 0);
+    game.players[0].set_actions(keys(&["ArrowRight"]));
+    game.step();
+    assert_eq!(game.players[0].body.position.x, start_x);
+    assert!(!game.fire(WeaponKind::Bazooka, 0.0, 1.0));
+    assert!(game.projectiles.is_empty());
+
+    for _ in 0..(SPAWN_TIME / FIXED_TIMESTEP) as usize + 2 {
+        game.step();
+    }
+    assert!(!game.players[0].is_spawning());
+    assert!(game.players[0].body.position.x > start_x);
+}
+
+#[test]
+fn lava_burns_and_water_drowns() {
+    let mut game = Game::new(200.0, 200.0);
+    let mut map = flat_map();
+    for row in 32..35 {
+        for col in 0..10 {
+            map.data[row][col] = Material::Lava;
+        }
+        for col in 10..20 {
+            map.data[row][col] = Material::Water;
+        }
+    }
+    game.set_map(map);
+    game.add_player(small_player(20.0, 150.0));
+    game.add_player(small_player(70.0, 150.0));
+    game.add_player(small_player(150.0, 150.0));
+
+    for _ in 0..60 {
+        game.step();
+    }
+    assert!(!game.players[0].is_alive());
+    assert!(game.players[1].is_alive() && game.players[1].health < MAX_HEALTH);
+    assert_eq!(game.players[2].health, MAX_HEALTH);
+    assert!(game
+        .take_events()
+        .contains(&GameEvent::PlayerDied { player: 0 }));
+
+    for _ in 0..240 {
+        game.step();
+    }
+    assert!(!game.players[1].is_alive());
+    assert!(game.players[2].is_alive());
+}
+
+#[test]
+fn worms_drown_under_the_water_line_of_generated_maps() {
+    let mut game = Game::new(800.0, 600.0);
+    game.set_map(Map::generate(800.0, 600.0, 42, TerrainPreset::Islands));
+    let water = game
+        .map
+        .water_level
+        .expect("islands are surrounded by water");
+    // стовпчик, де під лінією води ще повітря, а не суходіл
+    let x = (0..game.map.cols())
+        .map(|col| col as f64 * game.map.tile_size)
+        .find(|&x| game.map.can_move_to(x, water, 10.0, 10.0))
+        .expect("there is open water somewhere");
+    game.add_player(small_player(x, water + 5.0));
+
+    game.step();
+    assert!(game.players[0].health < MAX_HEALTH);
+    for _ in 0..300 {
+        game.step();
+    }
+    assert!(!game.players[0].is_alive());
+}
+
+#[test]
+fn rock_survives_explosions() {
+    let mut map = flat_map();
+    for col in 0..40 {
+        map.data[36][col] = Material::Rock;
+    }
+
+    map.carve_circle(100.0, 180.0, 15.0);
+
+    assert!(!map.is_solid_at(100.0, 177.0));
+    assert_eq!(map.material_at(100.0, 182.0), Material::Rock);
+    assert!(map.is_solid_at(100.0, 182.0));
+}
+
+#[test]
+fn liquids_are_not_solid_and_bouncy_floor_bounces() {
+    let mut map = flat_map();
+    for col in 0..40 {
+        map.data[34][col] = Material::Water;
+        map.data[35][col] = Material::Bouncy;
+    }
+    assert!(!map.is_solid_at(100.0, 172.0));
+
+    let mut player = small_player(50.0, 20.0);
+    let mut bounced = false;
+    for _ in 0..60 {
+        player.update(0.016, &map, 200.0);
+        bounced |= player.body.velocity_y < 0.0;
+    }
+    assert!(
+        bounced,
+        "falling onto a bouncy tile throws the player back up"
+    );
+}
+
+#[test]
+fn autotiling_picks_pieces_by_neighbours() {
+    let mut map = flat_map();
+    // стовпчик на землі: кути трави зверху, боки нижче
+    for row in 33..35 {
+        for col in 10..13 {
+            map.data[row][col] = Material::Dirt;
+        }
+    }
+
+    assert_eq!(tileset::tile_kind(&map, 10, 33), TileKind::TopLeft);
+    assert_eq!(tileset::tile_kind(&map, 11, 33), TileKind::Top);
+    assert_eq!(tileset::tile_kind(&map, 12, 33), TileKind::TopRight);
+    assert_eq!(tileset::tile_kind(&map, 10, 34), TileKind::Left);
+    assert_eq!(tileset::tile_kind(&map, 20, 35), TileKind::Top);
+    assert_eq!(
+        tileset::tile_kind(&map, 20, 37),
+        TileKind::Interior { depth: 2 }
+    );
+
+    // трава — верх профілю ґрунту, і вона тягнеться вздовж колонок
+    let tiles = TileSet::jungle();
+    let grass = tiles.source_rect(TileKind::Top, 20, 35);
+    assert_eq!(grass.y, tiles.profile.y);
+    assert_ne!(tiles.source_rect(TileKind::Top, 21, 35).x, grass.x);
+}
+
+#[test]
+fn player_falls_and_lands_on_ground() {
+    let map = flat_map();
+    let mut player = small_player(50.0, 20.0);
+
+    for _ in 0..120 {
+        player.update(0.016, &map, 200.0);
+    }
+
+    assert!(player.is_on_ground(&map));
+    assert_eq!(player.body.position.y + player.body.height, 35.0 * TILE);
+    assert_eq!(player.body.velocity_y, 0.0);
+}
+
+#[test]
+fn player_falls_into_crater() {
+    let mut map = flat_map();
+    let mut player = small_player(95.0, 165.0);
+    for _ in 0..10 {
+        player.update(0.016, &map, 200.0);
+    }
+    let standing_y = player.body.position.y;
+
+    map.carve_circle(100.0, 180.0, 15.0);
+    for _ in 0..60 {
+        player.update(0.016, &map, 200.0);
+    }
+
+    assert!(player.body.position.y > standing_y);
+}
+
+#[test]
+fn walls_block_horizontal_movement() {
+    let mut map = flat_map();
+    for row in 25..35 {
+        map.data[row][20] = Material::Dirt;
+    }
+    let mut player = small_player(80.0, 165.0);
+    player.set_actions(keys(&["ArrowRight"]));
+
+    for _ in 0..30 {
+        player.update(0.016, &map, 200.0);
+    }
+
+    assert!(player.body.position.x + player.body.width <= 20.0 * TILE);
+}
+
+#[test]
+fn walking_accelerates_and_friction_depends_on_ground() {
+    let map = flat_map();
+    let mut player = small_player(20.0, 165.0);
+    player.set_actions(keys(&["ArrowRight"]));
+
+    player.update(FIXED_TIMESTEP, &map, 200.0);
+    assert!(player.body.velocity_x > 0.0 && player.body.velocity_x < WALK_SPEED);
+    for _ in 0..30 {
+        player.update(FIXED_TIMESTEP, &map, 200.0);
+    }
+    assert_eq!(player.body.velocity_x, WALK_SPEED);
+
+    // на льоду після відпускання клавіші ковзаємо далі, ніж на ґрунті
+    let slide = |material: Material| {
+        let mut map = flat_map();
+        map.data[35].fill(material);
+        let mut player = small_player(20.0, 165.0);
+        player.body.velocity_x = WALK_SPEED;
+        for _ in 0..60 {
+            player.update(FIXED_TIMESTEP, &map, 200.0);
+        }
+        player.body.position.x - 20.0
+    };
+    assert!(slide(Material::Ice) > slide(Material::Dirt) * 3.0);
+    assert!(slide(Material::Dirt) > 0.0);
+}
+
+#[test]
+fn players_climb_small_steps_but_not_walls() {
+    let mut map = flat_map();
+    // уступ у два тайли, далі стіна в чотири
+    for col in 15..40 {
+        map.data[33][col] = Material::Dirt;
+        map.data[34][col] = Material::Dirt;
+    }
+    for row in 29..33 {
+        map.data[row][30] = Material::Dirt;
+    }
+    let mut player = small_player(40.0, 165.0);
+    player.set_actions(keys(&["ArrowRight"]));
+
+    for _ in 0..60 {
+        player.update(FIXED_TIMESTEP, &map, 200.0);
+    }
+
+    assert_eq!(player.body.position.y + player.body.height, 33.0 * TILE);
+    assert_eq!(player.body.position.x + player.body.width, 30.0 * TILE);
+}
+
+#[test]
+fn walking_down_a_staircase_keeps_feet_on_the_ground() {
+    let mut map = flat_map();
+    // сходинки по тайлу вниз кожні три колонки
+    for step in 0..5 {
+        for col in 0..(3 * (5 - step)) {
+            map.data[34 - step][col] = Material::Dirt;
+        }
+    }
+    let mut player = small_player(0.0, 140.0);
+    player.update(FIXED_TIMESTEP, &map, 200.0);
+    assert!(player.is_on_ground(&map));
+    player.set_actions(keys(&["ArrowRight"]));
+
+    for _ in 0..20 {
+        player.update(FIXED_TIMESTEP, &map, 200.0);
+        assert!(player.is_on_ground(&map), "x = {}", player.body.position.x);
+        assert!(player.take_landing().is_none());
+    }
+    assert_eq!(player.body.position.y + player.body.height, 35.0 * TILE);
+}
+
+#[test]
+fn impulses_are_sub_stepped_and_stop_at_walls() {
+    let mut map = flat_map();
+    for row in 25..35 {
+        map.data[row][20] = Material::Dirt;
+    }
+    let mut player = small_player(80.0, 165.0);
+    // за крок це 50 px — більше за стіну в один тайл
+    player.apply_impulse(3000.0, 0.0);
+
+    player.update(FIXED_TIMESTEP, &map, 200.0);
+
+    assert_eq!(player.body.position.x + player.body.width, 20.0 * TILE);
+    assert_eq!(player.body.velocity_x, 0.0);
+}
+
+#[test]
+fn explosions_hurt_by_distance_and_push_players_away() {
+    let mut game = Game::new(200.0, 200.0);
+    game.set_map(flat_map());
+    game.add_player(small_player(88.0, 165.0));
+    game.add_player(small_player(100.0, 165.0));
+    game.add_player(small_player(10.0, 165.0));
+
+    game.explode(90.0, 175.0, 15.0);
+
+    let [near, far, outside] = [0, 1, 2].map(|i| &game.players[i]);
+    assert!(near.health < far.health);
+    assert!(far.health < MAX_HEALTH);
+    assert_eq!(outside.health, MAX_HEALTH);
+    assert!(near.body.velocity_x > 0.0 && near.body.velocity_y < 0.0);
+    assert_eq!(outside.body.velocity_x, 0.0);
+}
+
+#[test]
+fn dead_players_leave_the_turn_rotation() {
+    let mut victim = small_player(0.0, 0.0);
+    assert!(!victim.take_damage(MAX_HEALTH - 1.0));
+    assert!(victim.take_damage(1.0));
+    assert!(!victim.take_damage(1.0), "уже мертвого вдруге не вбиваємо");
+
+    let mut game = Game::new(200.0, 200.0);
+    game.set_map(flat_map());
+    let animation = player::character_animation("worm.png");
+    let mut doomed = Player::new(Position::new(95.0, 165.0), Some(animation));
+    doomed.body.width = 10.0;
+    doomed.body.height = 10.0;
+    doomed.health = 1.0;
+    game.add_player(doomed);
+    game.add_player(small_player(10.0, 165.0));
+    assert_eq!(game.turns.current_player(), Some(0));
+
+    game.explode(100.0, 170.0, 15.0);
+    game.step();
+
+    assert!(!game.players[0].is_alive());
+    assert_eq!(game.turns.current_player(), Some(1));
+    assert!(game
+        .take_events()
+        .contains(&GameEvent::PlayerDied { player: 0 }));
+    game.end_turn();
+    assert_eq!(game.turns.current_player(), Some(1));
+
+    // смерть програється один раз і лишається на останньому кадрі
+    for _ in 0..120 {
+        game.step();
+    }
+    let animation = game.players[0].animation.as_ref().unwrap();
+    assert_eq!(animation.animation_row(), 5);
+    assert!(animation.is_finished());
+}
+
+#[test]
+fn hard_landings_cause_fall_damage() {
+    let map = flat_map();
+    let mut gentle = small_player(50.0, 150.0);
+    let mut hard = small_player(50.0, 20.0);
+    hard.body.velocity_y = 1200.0;
+
+    for _ in 0..60 {
+        gentle.update(FIXED_TIMESTEP, &map, 200.0);
+        hard.update(FIXED_TIMESTEP, &map, 200.0);
+    }
+
+    assert_eq!(gentle.health, MAX_HEALTH);
+    assert!(hard.health < MAX_HEALTH);
+    assert!(hard.is_on_ground(&map));
+}
+
+#[test]
+fn landing_emits_event_with_impact_speed_and_shows_landing_frame() {
+    let mut game = Game::new(200.0, 200.0);
+    game.set_map(flat_map());
+    let animation = player::character_animation("worm.png");
+    let mut jumper = Player::new(Position::new(50.0, 20.0), Some(animation));
+    jumper.body.width = 10.0;
+    jumper.body.height = 10.0;
+    // з такої висоти удар слабший за поріг — лише кадр приземлення, без болю
+    jumper.fall_damage = FallDamage {
+        min_speed: 1000.0,
+        damage_per_speed: 0.1,
+        max_damage: 20.0,
+    };
+    game.add_player(jumper);
+
+    let mut landing = None;
+    for _ in 0..120 {
+        game.step();
+        landing = game.take_events().into_iter().find_map(|e| match e {
+            GameEvent::PlayerLanded {
+                impact_speed,
+                damage,
+                ..
+            } => Some((impact_speed, damage)),
+            _ => None,
+        });
+        if landing.is_some() {
+            break;
+        }
+    }
+
+    let (impact_speed, damage) = landing.expect("player lands");
+    assert!(impact_speed > 500.0);
+    assert_eq!(damage, 0.0);
+    assert_eq!(game.players[0].health, MAX_HEALTH);
+    let fall_damage = game.players[0].fall_damage;
+    assert_eq!(fall_damage.damage(1100.0), 10.0);
+    assert_eq!(fall_damage.damage(5000.0), 20.0);
+    game.step();
+    let animation = game.players[0].animation.as_ref().unwrap();
+    assert_eq!(
+        (animation.animation_row(), animation.current_frame()),
+        (3, 2)
+    );
+
+    // стояння на землі нових приземлень не дає
+    for _ in 0..30 {
+        game.step();
+    }
+    assert!(!game
+        .take_events()
+        .iter()
+        .any(|e| matches!(e, GameEvent::PlayerLanded { .. })));
+    assert_eq!(
+        game.players[0].animation.as_ref().unwrap().animation_row(),
+        1
+    );
+}
+
+/// Запам'ятовує прямокутники, залиті заданим кольором.
+struct RectRecorder {
+    color: &'static str,
+    rects: RefCell<Vec<Rect>>,
+}
+
+impl Renderer for RectRecorder {
+    fn clear(&self, _width: f64, _height: f64) {}
+    fn fill_rect(&self, rect: Rect, color: &str) {
+        if color == self.color {
+            self.rects.borrow_mut().push(rect);
+        }
+    }
+    fn fill_circle(&self, _x: f64, _y: f64, _radius: f64, _color: &str) {}
+    fn draw_sprite(&self, _sprite: &str, _source: Rect, _dest: Rect, _flip_x: bool) {}
+}
+
+const CRATE_SIZE: f64 = 24.0;
+
+/// Ящик: лише тримає тіло, уся фізика — від `PhysicsBody` і трейтів.
+struct SupplyCrate {
+    body: PhysicsBody,
+}
+
+impl SupplyCrate {
+    fn new(position: Position) -> Self {
+        Self {
+            body: PhysicsBody::new(position, CRATE_SIZE, CRATE_SIZE),
+        }
+    }
+}
+
+impl HasBody for SupplyCrate {
+    fn body(&self) -> &PhysicsBody {
+        &self.body
+    }
+
+    fn body_mut(&mut self) -> &mut PhysicsBody {
+        &mut self.body
+    }
+}
+
+impl CanvasObject for SupplyCrate {
+    fn draw(&self, renderer: &dyn Renderer) {
+        let position = self.body.position;
+        let rect = Rect::new(position.x, position.y, self.body.width, self.body.height);
+        renderer.fill_rect(rect, "saddlebrown");
+    }
+}
+
+#[test]
+fn objects_with_a_body_get_gravity_and_collisions() {
+    let mut game = Game::new(200.0, 200.0);
+    game.set_map(flat_map());
+    let ground = 35.0 * TILE;
+    game.add_object(Box::new(SupplyCrate::new(Position::new(100.0, 0.0))));
+    game.add_object(Box::new(SupplyCrate::new(Position::new(
+        20.0,
+        ground - CRATE_SIZE,
+    ))));
+
+    for _ in 0..120 {
+        game.step();
+    }
+    let renderer = RectRecorder {
+        color: "saddlebrown",
+        rects: RefCell::new(Vec::new()),
+    };
+    game.draw(&renderer);
+    let rects = renderer.rects.borrow();
+    assert_eq!(rects.len(), 2);
+    assert_eq!(rects[0].y + rects[0].height, ground);
+
+    // вибух ліворуч відкидає другий ящик; далі він падає і зупиняється тертям
+    game.explode(10.0, ground - 5.0, 20.0);
+    assert!(game.objects[1].body().velocity_x > 0.0);
+    for _ in 0..120 {
+        game.step();
+    }
+    let body = game.objects[1].body();
+    assert!(body.position.x > 20.0);
+    assert_eq!(body.velocity_x, 0.0);
+    assert!(body.is_on_ground(&game.map));
+    assert_eq!(game.objects[0].body().position.x, 100.0);
+
+    // ящик не знає про фізику — лише тримає тіло, а трейти дають решту
+    let map = flat_map();
+    let mut supply = SupplyCrate::new(Position::new(20.0, ground - CRATE_SIZE));
+    assert!(supply.is_on_ground(&map));
+    assert!(!supply.try_move_y(1.0, &map, 200.0));
+}
+
+/// Запам'ятовує, які спрайти і куди малювали, по порядку.
+#[derive(Default)]
+struct SpriteRecorder {
+    sprites: RefCell<Vec<(String, Rect)>>,
+}
+
+impl Renderer for SpriteRecorder {
+    fn clear(&self, _width: f64, _height: f64) {}
+    fn fill_rect(&self, _rect: Rect, _color: &str) {}
+    fn fill_circle(&self, _x: f64, _y: f64, _radius: f64, _color: &str) {}
+    fn draw_sprite(&self, sprite: &str, _source: Rect, dest: Rect, _flip_x: bool) {
+        self.sprites.borrow_mut().push((sprite.to_string(), dest));
+    }
+}
+
+#[test]
+fn shadows_fall_on_the_ground_and_shrink_with_height() {
+    let map = flat_map();
+    let ground = 35.0 * TILE;
+
+    let standing = small_player(50.0, ground - 10.0);
+    let shadow = standing.shadow_rect(&map, 1.0).unwrap();
+    assert_eq!(shadow.y + shadow.height / 2.0, ground);
+    assert_eq!(shadow.x + shadow.width / 2.0, 55.0);
+
+    let jumping = small_player(50.0, ground - 110.0);
+    let high = jumping.shadow_rect(&map, 1.0).unwrap();
+    assert_eq!(high.y + high.height / 2.0, ground);
+    assert!(high.width < shadow.width && high.height < shadow.height);
+
+    let mut pit = vec![vec![Material::Air; 40]; 40];
+    for row in pit.iter_mut().skip(35) {
+        row.fill(Material::Dirt);
+        row[10..14].fill(Material::Air);
+    }
+    let pit = Map::from_tiles(TILE, pit);
+    assert!(small_player(50.0, ground - 40.0)
+        .shadow_rect(&pit, 1.0)
+        .is_none());
+
+    let mut game = Game::new(200.0, 200.0);
+    game.map = map;
+    game.add_player(Player::new(
+        Position::new(50.0, ground - 64.0),
+        Some(player::character_animation("any")),
+    ));
+    let renderer = SpriteRecorder::default();
+    game.draw(&renderer);
+    let sprites = renderer.sprites.borrow();
+    let shadow = sprites
+        .iter()
+        .position(|(sprite, _)| sprite == assets::CHARACTER_SHADOW);
+    let worm = sprites.iter().position(|(sprite, _)| {
+        sprite.starts_with("character/") && sprite != assets::CHARACTER_SHADOW
+    });
+    assert!(shadow.unwrap() < worm.unwrap());
+}
+
+#[test]
+fn asset_manifest_is_preloaded_into_a_keyed_cache() {
+    let manifest = AssetManifest::game();
+    assert!(manifest.validate().is_ok());
+    assert_eq!(
+        manifest.get(&TileSet::jungle().sprite).map(|e| e.kind),
+        Some(AssetKind::Image)
+    );
+    for color in assets::CHARACTER_COLORS.iter() {
+        let entry = manifest.get(&assets::character_key(color)).unwrap();
+        assert!(entry
+            .path
+            .ends_with(&format!("character_20x20_{}.png", color)));
+    }
+
+    let twice = AssetManifest::new()
+        .image("boom", "a.png")
+        .sound("boom", "b.ogg");
+    assert_eq!(
+        twice.validate(),
+        Err(AssetError::DuplicateKey("boom".to_string()))
+    );
+
+    let manifest = AssetManifest::new()
+        .image("tiles", "tiles.png")
+        .sound("boom", "boom.ogg");
+    let mut cache: AssetCache<&str, &str> = AssetCache::new();
+    assert_eq!(cache.progress(&manifest).fraction(), 0.0);
+    assert_eq!(
+        cache.image("tiles"),
+        Err(AssetError::NotLoaded("tiles".to_string()))
+    );
+
+    cache.insert("tiles", Asset::Image("tiles image"));
+    let progress = cache.progress(&manifest);
+    assert_eq!((progress.loaded, progress.total), (1, 2));
+    assert!(!progress.is_done());
+    let pending: Vec<_> = cache
+        .pending(&manifest)
+        .into_iter()
+        .map(|e| e.key)
+        .collect();
+    assert_eq!(pending, vec!["boom".to_string()]);
+
+    cache.insert("boom", Asset::Sound("boom sound"));
+    assert!(cache.progress(&manifest).is_done());
+    assert_eq!(cache.image("tiles"), Ok(&"tiles image"));
+    assert_eq!(cache.sound("boom"), Ok(&"boom sound"));
+    assert!(matches!(
+        cache.sound("tiles"),
+        Err(AssetError::WrongKind {
+            expected: AssetKind::Sound,
+            ..
+        })
+    ));
+    assert_eq!(cache.images().count(), 1);
+}
+
+#[test]
+fn animation_sets_are_data_driven() {
+    let json = r#"{
+        "frame_width": 20, "frame_height": 20, "initial": "idle",
+        "clips": [
+            { "name": "idle", "row": 1, "frames": 2, "fps": 10 },
+            { "name": "aim", "row": 6, "first_frame": 1, "frames": 3, "fps": 10,
+              "mode": "once", "events": { "2": "steady" } },
+            { "name": "death", "row": 5, "frames": 2, "fps": 10, "mode": "hold_last" }
+        ],
+        "transitions": [
+            { "to": "death", "when": ["dead"], "interrupt": true },
+            { "to": "aim", "when": ["aiming"] },
+            { "to": "idle" }
+        ]
+    }"#;
+    let set = Rc::new(AnimationSet::from_json(json).unwrap());
+    let mut animation = Animation::new("worm.png", set);
+
+    animation.apply_transitions(&["aiming"]);
+    assert_eq!(animation.clip_name(), "aim");
+    assert_eq!(
+        (animation.animation_row(), animation.current_frame()),
+        (6, 1)
+    );
+
+    // неповторюваний кліп не перериваємо, поки не дограє
+    let mut events = Vec::new();
+    for _ in 0..3 {
+        animation.apply_transitions(&[]);
+        animation.update(0.1);
+        events.extend(animation.events().iter().cloned());
+    }
+    assert_eq!(events, vec!["steady".to_string()]);
+    assert!(animation.is_finished());
+    animation.apply_transitions(&[]);
+    assert_eq!(animation.clip_name(), "idle");
+
+    animation.apply_transitions(&["dead"]);
+    for _ in 0..10 {
+        animation.update(0.1);
+        animation.apply_transitions(&["aiming"]);
+    }
+    assert_eq!(
+        (animation.clip_name(), animation.current_frame()),
+        ("death", 1)
+    );
+
+    let broken = json.replace(r#""to": "aim""#, r#""to": "aiming""#);
+    assert!(matches!(
+        AnimationSet::from_json(&broken),
+        Err(AnimationError::UnknownClip(name)) if name == "aiming"
+    ));
+}
+
+#[test]
+fn walking_worm_fires_footstep_events() {
+    let map = flat_map();
+    let mut player = small_player(20.0, 165.0);
+    player.animation = Some(player::character_animation("worm.png"));
+    player.set_actions(keys(&["ArrowRight"]));
+
+    // пів секунди — кадри 1..=5 циклу ходьби, кроки на 1 і 4
+    let mut footsteps = 0;
+    for _ in 0..30 {
+        player.update(FIXED_TIMESTEP, &map, 200.0);
+        let animation = player.animation.as_ref().unwrap();
+        assert_eq!(animation.clip_name(), "walk");
+        footsteps += animation
+            .events()
+            .iter()
+            .filter(|e| *e == "footstep")
+            .count();
+    }
+    assert_eq!(footsteps, 2);
+}
+
+#[test]
+fn bundled_character_animations_parse() {
+    let set = AnimationSet::from_json(player::NUCLEAR_LEAK_ANIMATIONS).unwrap();
+    for clip in [
+        "spawn", "idle", "walk", "rise", "fall", "land", "hurt", "death",
+    ] {
+        assert!(set.clip_index(clip).is_some(), "{}", clip);
+    }
+}
+
+#[test]
+fn animation_frame_events_reach_the_game() {
+    let mut game = Game::new(200.0, 200.0);
+    game.set_map(flat_map());
+    let mut worm = small_player(20.0, 165.0);
+    worm.animation = Some(player::character_animation("worm.png"));
+    game.add_player(worm);
+    game.players[0].set_actions(keys(&["ArrowRight"]));
+
+    let mut footsteps = 0;
+    for _ in 0..30 {
+        game.step();
+        footsteps += game
+            .take_events()
+            .iter()
+            .filter(|e| {
+                **e == GameEvent::PlayerAnimation {
+                    player: 0,
+                    event: "footstep".to_string(),
+                }
+            })
+            .count();
+    }
+    assert_eq!(footsteps, 2);
+}
+
+#[test]
+fn key_bindings_can_be_rebound() {
+    let mut bindings = KeyBindings::default();
+    assert_eq!(bindings.action_for("KeyF"), Some(Action::Fire));
+
+    bindings.bind("KeyJ", Action::Fire);
+    bindings.unbind("KeyF");
+
+    assert_eq!(bindings.action_for("KeyF"), None);
+    assert_eq!(bindings.keys_for(Action::Fire), vec!["Enter", "KeyJ"]);
+}
+
+#[test]
+fn gamepad_sticks_respect_dead_zone() {
+    let mapping = GamepadMapping { dead_zone: 0.3 };
+    let mut buttons = vec![false; 16];
+
+    assert!(mapping.actions(&[0.2, -0.1], &buttons).is_empty());
+
+    buttons[0] = true;
+    let actions = mapping.actions(&[-0.8, -0.5], &buttons);
+    assert_eq!(
+        actions,
+        HashSet::from([Action::MoveLeft, Action::AimUp, Action::Jump])
+    );
+}
+
+#[test]
+fn gamepads_take_free_teams_and_release_them() {
+    let mut pads = GamepadAssignments::default();
+    pads.sync(&[0, 2]);
+    assert_eq!(pads.team_of(0), Some(0));
+    assert_eq!(pads.team_of(2), Some(1));
+
+    // перший геймпад відключили, новий займає звільнену команду
+    pads.sync(&[2, 3]);
+    assert_eq!(pads.team_of(0), None);
+    assert_eq!(pads.team_of(2), Some(1));
+    assert_eq!(pads.team_of(3), Some(0));
+    assert_eq!(pads.pads_for_team(0).collect::<Vec<_>>(), vec![3]);
+}
+
+#[test]
+fn pressed_edges_survive_until_a_step_consumes_them() {
+    let mut input = InputState::default();
+    input.set_held(keys(&["Space"]));
+    // відпустили ще до кроку симуляції — фронт не губиться
+    input.set_held(HashSet::new());
+    assert!(input.was_pressed(Action::Jump));
+    assert!(!input.is_held(Action::Jump));
+
+    input.end_step();
+    input.set_held(keys(&["Space"]));
+    input.end_step();
+    input.set_held(keys(&["Space"]));
+    assert!(
+        !input.was_pressed(Action::Jump),
+        "holding is not a new press"
+    );
+}
+
+#[test]
+fn holding_jump_jumps_only_once() {
+    let map = flat_map();
+    let mut player = small_player(50.0, 165.0);
+    for _ in 0..10 {
+        player.update(0.016, &map, 200.0);
+    }
+
+    let mut jumps = 0;
+    for _ in 0..120 {
+        player.set_actions(keys(&["Space"]));
+        let was_grounded = player.body.velocity_y == 0.0;
+        player.update(0.016, &map, 200.0);
+        if was_grounded && player.body.velocity_y < 0.0 {
+            jumps += 1;
+        }
+    }
+
+    assert_eq!(jumps, 1);
+}
+
+#[test]
+fn fire_action_launches_current_weapon() {
+    let mut game = Game::new(800.0, 600.0);
+    game.add_player(small_player(50.0, 50.0));
+
+    game.players[0].set_actions(keys(&["KeyF"]));
+    game.step();
+
+    assert_eq!(game.projectiles.len(), 1);
+    assert!(!game.turns.can_fire());
+    // постріл з клавіатури несе справжні кут, зброю і силу — для мережі
+    assert!(game.take_events().contains(&GameEvent::ShotFired {
+        weapon: WeaponKind::Bazooka,
+        angle: game.players[0].aim_angle,
+        power: DEFAULT_FIRE_POWER,
+    }));
+}
+
+#[test]
+fn snapshots_set_health_in_both_directions() {
+    let mut game = Game::new(200.0, 200.0);
+    game.set_map(flat_map());
+    let mut worm = small_player(50.0, 165.0);
+    worm.animation = Some(player::character_animation("worm.png"));
+    worm.health = 40.0;
+    game.add_player(worm);
+    let mut state = game.snapshot();
+
+    state[0].health = 100.0;
+    game.apply_snapshot(&state);
+    game.step();
+    assert_eq!(game.players[0].health, 100.0);
+    assert_ne!(
+        game.players[0].animation.as_ref().unwrap().clip_name(),
+        "hurt"
+    );
+
+    state[0].health = 70.0;
+    game.apply_snapshot(&state);
+    game.step();
+    assert_eq!(game.players[0].health, 70.0);
+    assert_eq!(
+        game.players[0].animation.as_ref().unwrap().clip_name(),
+        "hurt"
+    );
+}
+
+#[test]
+fn remote_fire_key_is_ignored_without_fire_input() {
+    let mut game = Game::new(800.0, 600.0);
+    game.add_player(small_player(50.0, 50.0));
+
+    game.apply_remote_input(vec![Action::Fire], None);
+    game.step();
+    assert!(game.projectiles.is_empty());
+
+    let fire = FireInput {
+        weapon: "grenade".to_string(),
+        angle: 0.5,
+        power: 0.3,
+    };
+    game.apply_remote_input(vec![], Some(fire));
+    assert_eq!(game.projectiles.len(), 1);
+    assert_eq!(game.projectiles[0].kind, WeaponKind::Grenade);
+}
+
+#[test]
+fn turns_rotate_between_teams_after_firing() {
+    let mut game = Game::new(800.0, 600.0);
+    game.add_player(small_player(50.0, 50.0));
+    game.add_player(small_player(300.0, 50.0));
+    game.turns.retreat_time = 0.1;
+    assert_eq!(game.turns.current_player(), Some(0));
+
+    assert!(game.fire(WeaponKind::Grenade, 0.5, 0.0));
+    assert_eq!(game.turns.phase(), TurnPhase::Retreat);
+    assert!(!game.turns.can_fire());
+
+    // граната з запалом 3 с має вибухнути, і лише потім хід переходить далі
+    for _ in 0..300 {
+        game.step();
+    }
+
+    assert_eq!(game.turns.current_player(), Some(1));
+    let events = game.take_events();
+    assert!(events
+        .iter()
+        .any(|e| matches!(e, GameEvent::Explosion { .. })));
+    assert!(events.contains(&GameEvent::TurnChanged { team: 1, player: 1 }));
+}
+
+#[test]
+fn teams_get_unique_colours_and_paint_their_worms() {
+    let worm = || {
+        Player::new(
+            Position::new(0.0, 0.0),
+            Some(player::character_animation("any")),
+        )
+    };
+    let sprite = |game: &Game, index: usize| {
+        game.players[index]
+            .animation
+            .as_ref()
+            .unwrap()
+            .sprite()
+            .to_string()
+    };
+
+    let mut game = Game::new(800.0, 600.0);
+    let red = game.create_team("Червоні", "red").unwrap();
+    assert_eq!(
+        game.create_team("Ще червоні", "red"),
+        Err(TeamError::ColorTaken("red".to_string()))
+    );
+    assert_eq!(
+        game.create_team("Бірюзові", "teal"),
+        Err(TeamError::UnknownColor("teal".to_string()))
+    );
+    game.add_player_to_team(red, worm());
+    game.add_player_to_team(red, worm());
+    assert_eq!(sprite(&game, 0), assets::character_key("red"));
+    assert_eq!(sprite(&game, 1), sprite(&game, 0));
+
+    // команда без кольору бере перший вільний
+    game.add_player(worm());
+    assert_eq!(game.turns.teams[1].color, "black");
+    assert_eq!(sprite(&game, 2), assets::character_key("black"));
+
+    // сервер віддав червоний другій команді — перша забирає її старий колір
+    game.assign_team(1, "Гості", "red").unwrap();
+    assert_eq!(game.turns.teams[1].name, "Гості");
+    assert_eq!(game.turns.teams[1].color, "red");
+    assert_eq!(game.turns.teams[0].color, "black");
+    assert_eq!(sprite(&game, 0), assets::character_key("black"));
+    assert_eq!(sprite(&game, 2), assets::character_key("red"));
+    assert_eq!(
+        game.assign_team(5, "Ніхто", "blue"),
+        Err(TeamError::UnknownTeam(5))
+    );
+
+    let join = Message::Join {
+        client_id: 1,
+        slot: 1,
+        name: "Гості".to_string(),
+        color: Some("red".to_string()),
+    };
+    assert_eq!(
+        protocol::decode(&protocol::encode(join.clone())).unwrap(),
+        join
+    );
+}
+
+#[test]
+fn shells_fly_through_dead_players() {
+    let map = flat_map();
+    let fly = |target: &Player| {
+        let mut projectile = Projectile::launch(WeaponKind::Bazooka, 20.0, 150.0, 0.3, 0.4);
+        (0..200)
+            .find_map(|_| projectile.update(0.016, &map, std::slice::from_ref(target), 0.0))
+            .expect("bazooka detonates")
+    };
+
+    let mut target = small_player(120.0, 140.0);
+    let hit = fly(&target);
+    assert!(hit.x <= 130.0, "{:?}", hit.x);
+
+    target.take_damage(MAX_HEALTH);
+    let miss = fly(&target);
+    assert!(miss.x > 130.0, "{:?}", miss.x);
+}
+
+#[test]
+fn bazooka_detonates_on_terrain() {
+    let map = flat_map();
+    let mut projectile = Projectile::launch(WeaponKind::Bazooka, 20.0, 100.0, -0.5, 0.5);
+
+    let detonation = (0..200).find_map(|_| projectile.update(0.016, &map, &[], 0.0));
+
+    let detonation = detonation.expect("bazooka hits the ground");
+    assert!(detonation.y >= 35.0 * TILE - 1.0);
+    assert!(projectile.finished);
+}
+
+#[test]
+fn update_runs_fixed_steps_and_keeps_remainder() {
+    let mut game = Game::new(800.0, 600.0);
+
+    assert_eq!(game.update(FIXED_TIMESTEP * 2.5), 2);
+    assert!((game.interpolation_alpha() - 0.5).abs() < 1e-9);
+    assert_eq!(game.update(FIXED_TIMESTEP * 0.6), 1);
+    // довга пауза (вкладка у фоні) не змушує наздоганяти вічність
+    assert!(game.update(10.0) <= 16);
+}
+
+#[test]
+fn game_draws_with_null_renderer() {
+    let mut game = Game::new(800.0, 600.0);
+    game.add_player(small_player(50.0, 50.0));
+    game.update(0.05);
+    game.draw(&NullRenderer);
+}
+
+/// Рахує виклики малювання і вдає, що тримає шари в кеші.
+#[derive(Default)]
+struct CountingRenderer {
+    fills: Cell<usize>,
+    layer_created: Cell<bool>,
+}
+
+impl Renderer for CountingRenderer {
+    fn clear(&self, _width: f64, _height: f64) {}
+    fn fill_rect(&self, _rect: Rect, _color: &str) {
+        self.fills.set(self.fills.get() + 1);
+    }
+    fn fill_circle(&self, _x: f64, _y: f64, _radius: f64, _color: &str) {}
+    fn draw_sprite(&self, _sprite: &str, _source: Rect, _dest: Rect, _flip_x: bool) {}
+    fn begin_layer(&self, _layer: &str, _width: f64, _height: f64) -> LayerState {
+        if self.layer_created.replace(true) {
+            LayerState::Cached
+        } else {
+            LayerState::Fresh
+        }
+    }
+}
+
+#[test]
+fn terrain_layer_redraws_only_dirty_tiles() {
+    let mut game = Game::new(200.0, 200.0);
+    game.map = flat_map();
+    let renderer = CountingRenderer::default();
+    let solid = 5 * 40;
+
+    game.draw(&renderer);
+    assert_eq!(renderer.fills.get(), solid);
+
+    renderer.fills.set(0);
+    game.draw(&renderer);
+    assert_eq!(
+        renderer.fills.get(),
+        0,
+        "unchanged terrain comes from the cache"
+    );
+
+    game.explode(100.0, 180.0, 8.0);
+    renderer.fills.set(0);
+    game.draw(&renderer);
+    assert!(renderer.fills.get() > 0 && renderer.fills.get() < solid / 2);
+}
+
+#[test]
+fn camera_converts_coordinates_and_zooms_around_cursor() {
+    let mut camera = Camera::new(400.0, 300.0, 2000.0, 1000.0);
+    camera.center_on(&Position::new(1000.0, 500.0));
+    assert_eq!((camera.x, camera.y), (800.0, 350.0));
+
+    let cursor = Position::new(100.0, 50.0);
+    let under_cursor = camera.screen_to_world(&cursor);
+    camera.zoom_at(&cursor, 2.0);
+    assert_eq!(camera.zoom, 2.0);
+    assert_eq!(camera.screen_to_world(&cursor), under_cursor);
+    assert_eq!(camera.world_to_screen(&under_cursor), cursor);
+
+    // за край світу камера не виїжджає
+    camera.pan(-10_000.0, -10_000.0);
+    assert_eq!((camera.x, camera.y), (0.0, 0.0));
+}
+
+#[test]
+fn camera_follows_the_active_player_in_a_large_world() {
+    let mut game = Game::with_world(2000.0, 600.0, 400.0, 300.0);
+    game.add_player(small_player(1500.0, 50.0));
+
+    for _ in 0..120 {
+        game.update(FIXED_TIMESTEP);
+    }
+
+    let target = game.camera_target().unwrap();
+    let on_screen = game.camera.world_to_screen(&target);
+    assert!((on_screen.x - 200.0).abs() < 5.0, "{:?}", on_screen);
+    assert!(on_screen.y >= 0.0 && on_screen.y <= 300.0);
+}
+
+#[test]
+fn resizing_keeps_the_world_intact() {
+    let mut game = Game::with_world(2000.0, 600.0, 800.0, 600.0);
+    game.add_player(small_player(300.0, 50.0));
+    game.explode(500.0, 450.0, 40.0);
+    game.fire(WeaponKind::Grenade, 0.5, 0.5);
+    for _ in 0..10 {
+        game.step();
+    }
+    let tiles = game.map.data.clone();
+    let position = game.players[0].body.position;
+
+    game.resize_viewport(320.0, 200.0);
+
+    assert_eq!(game.map.data, tiles);
+    assert_eq!(game.players[0].body.position, position);
+    assert_eq!(game.projectiles.len(), 1);
+    assert_eq!(game.camera.viewport_width, 320.0);
+    assert_eq!(game.map.width(), 2000.0);
+}
+
+#[test]
+fn protocol_round_trip_and_version_check() {
+    let message = Message::Chat {
+        client_id: 3,
+        text: "привіт".to_string(),
+    };
+    let text = protocol::encode(message.clone());
+    assert_eq!(protocol::decode(&text).unwrap(), message);
+
+    let old = text.replace(
+        &format!("\"version\":{}", protocol::PROTOCOL_VERSION),
+        "\"version\":0",
+    );
+    assert!(matches!(
+        protocol::decode(&old),
+        Err(ProtocolError::VersionMismatch { .. })
+    ));
+}
@@ -0,0 +1,221 @@
+//! Локальний тестовий сервер для гри по мережі.
+//!
+//! Роздає клієнтам слоти (індекси команд) і пересилає повідомлення протоколу
+//! всім іншим учасникам. Запуск: `cargo run -p wararar-server`, після чого
+//! дві вкладки браузера підключаються до `ws://127.0.0.1:3000/ws`.
+
+use std::io::ErrorKind;
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tungstenite::{accept, Message as WsMessage, WebSocket};
+use wararar_core::assets::CHARACTER_COLORS;
+use wararar_core::models::terrain::TerrainPreset;
+use wararar_core::models::turn;
+use wararar_core::protocol::{self, Message, ProtocolError};
+
+const ADDRESS: &str = "127.0.0.1:3000";
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+struct Client {
+    id: u32,
+    slot: usize,
+    name: String,
+    color: String,
+    outbox: Sender<String>,
+}
+
+struct Lobby {
+    clients: Vec<Client>,
+    next_id: u32,
+    seed: u64, // одна карта на все лобі
+}
+
+impl Lobby {
+    fn new(seed: u64) -> Self {
+        Self {
+            clients: Vec::new(),
+            next_id: 0,
+            seed,
+        }
+    }
+
+    fn free_slot(&self) -> usize {
+        (0..)
+            .find(|slot| !self.clients.iter().any(|c| c.slot == *slot))
+            .unwrap_or_default()
+    }
+
+    /// Бажаний колір, якщо він вільний, інакше перший вільний.
+    fn assign_color(&self, wanted: Option<&str>) -> String {
+        let taken = self.clients.iter().map(|c| c.color.as_str());
+        wanted
+            .filter(|color| turn::check_color(color, taken.clone()).is_ok())
+            .or_else(|| turn::free_color(taken))
+            .unwrap_or(CHARACTER_COLORS[0]) // гравців більше, ніж кольорів
+            .to_string()
+    }
+
+    fn broadcast_except(&self, sender: u32, message: Message) {
+        let text = protocol::encode(message);
+        for client in self.clients.iter().filter(|c| c.id != sender) {
+            let _ = client.outbox.send(text.clone());
+        }
+    }
+}
+
+type SharedLobby = Arc<Mutex<Lobby>>;
+
+fn main() {
+    let listener = TcpListener::bind(ADDRESS).expect("cannot bind server address");
+    println!("🌐 Сервер слухає ws://{}/ws", ADDRESS);
+
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64);
+    println!("🗺️ Seed карти: {}", seed);
+    let lobby = Arc::new(Mutex::new(Lobby::new(seed)));
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let lobby = Arc::clone(&lobby);
+                thread::spawn(move || handle_connection(stream, lobby));
+            }
+            Err(e) => eprintln!("❌ Помилка з'єднання: {}", e),
+        }
+    }
+}
+
+fn handle_connection(stream: TcpStream, lobby: SharedLobby) {
+    let mut socket = match accept(stream) {
+        Ok(socket) => socket,
+        Err(e) => {
+            eprintln!("❌ Рукостискання не вдалося: {}", e);
+            return;
+        }
+    };
+    let _ = socket.get_mut().set_read_timeout(Some(POLL_INTERVAL));
+
+    let (outbox, inbox) = mpsc::channel::<String>();
+    let mut client_id = None;
+
+    loop {
+        match socket.read() {
+            Ok(WsMessage::Text(text)) => match protocol::decode(&text) {
+                Ok(message) => {
+                    if !handle_message(&mut socket, &lobby, &outbox, &mut client_id, message) {
+                        break;
+                    }
+                }
+                Err(e @ ProtocolError::VersionMismatch { .. }) => {
+                    eprintln!("❌ {}", e);
+                    break;
+                }
+                Err(e) => eprintln!("⚠️ {}", e),
+            },
+            Ok(WsMessage::Close(_)) => break,
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e))
+                if e.kind() == ErrorKind::WouldBlock || e.kind() == ErrorKind::TimedOut => {}
+            Err(_) => break,
+        }
+
+        while let Ok(text) = inbox.try_recv() {
+            if socket.send(WsMessage::Text(text)).is_err() {
+                break;
+            }
+        }
+    }
+
+    if let Some(id) = client_id {
+        let mut lobby = lobby.lock().unwrap();
+        lobby.clients.retain(|c| c.id != id);
+        lobby.broadcast_except(id, Message::Leave { client_id: id });
+        println!("👋 Клієнт {} вийшов", id);
+    }
+}
+
+/// Повертає `false`, коли з'єднання треба закрити.
+fn handle_message(
+    socket: &mut WebSocket<TcpStream>,
+    lobby: &SharedLobby,
+    outbox: &Sender<String>,
+    client_id: &mut Option<u32>,
+    message: Message,
+) -> bool {
+    let mut lobby = lobby.lock().unwrap();
+
+    let Some(id) = *client_id else {
+        if let Message::Join { name, color, .. } = message {
+            let id = lobby.next_id;
+            lobby.next_id += 1;
+            let slot = lobby.free_slot();
+            let color = lobby.assign_color(color.as_deref());
+            *client_id = Some(id);
+
+            let mut greeting = vec![Message::Welcome {
+                client_id: id,
+                slot,
+                color: color.clone(),
+                seed: lobby.seed,
+                preset: TerrainPreset::default().name().to_string(),
+            }];
+            greeting.extend(lobby.clients.iter().map(|c| Message::Join {
+                client_id: c.id,
+                slot: c.slot,
+                name: c.name.clone(),
+                color: Some(c.color.clone()),
+            }));
+            for message in greeting {
+                let _ = outbox.send(protocol::encode(message));
+            }
+
+            lobby.broadcast_except(
+                id,
+                Message::Join {
+                    client_id: id,
+                    slot,
+                    name: name.clone(),
+                    color: Some(color.clone()),
+                },
+            );
+            println!(
+                "✅ Клієнт {} ({}, {}) зайняв слот {}",
+                id, name, color, slot
+            );
+            lobby.clients.push(Client {
+                id,
+                slot,
+                name,
+                color,
+                outbox: outbox.clone(),
+            });
+        }
+        // до Join інші повідомлення ігноруємо
+        return true;
+    };
+
+    // підставляємо справжній id відправника, щоб клієнти не могли його підробити
+    let message = match message {
+        Message::Input { actions, fire, .. } => Message::Input {
+            client_id: id,
+            actions,
+            fire,
+        },
+        Message::Chat { text, .. } => Message::Chat {
+            client_id: id,
+            text,
+        },
+        Message::Leave { .. } => {
+            let _ = socket.close(None);
+            return false;
+        }
+        Message::Join { .. } | Message::Welcome { .. } => return true,
+        other => other,
+    };
+    lobby.broadcast_except(id, message);
+    true
+}
@@ -0,0 +1,65 @@
+use js_sys::{Function, Promise};
+use wasm_bindgen::prelude::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{window, HtmlAudioElement, HtmlElement, HtmlImageElement};
+
+use wararar_core::assets::{Asset, AssetCache, AssetEntry, AssetError, AssetKind};
+
+pub type Assets = AssetCache<HtmlImageElement, HtmlAudioElement>;
+
+/// Завантажує один запис маніфесту.
+pub async fn load(
+    entry: &AssetEntry,
+) -> Result<Asset<HtmlImageElement, HtmlAudioElement>, AssetError> {
+    let loaded = match entry.kind {
+        AssetKind::Image => load_image(&entry.path).await.map(Asset::Image),
+        AssetKind::Sound => load_sound(&entry.path).await.map(Asset::Sound),
+    };
+    loaded.map_err(|err| AssetError::LoadFailed {
+        key: entry.key.clone(),
+        path: entry.path.clone(),
+        reason: err.as_string().unwrap_or_else(|| format!("{:?}", err)),
+    })
+}
+
+/// Створює `<img>` і чекає, поки картинка завантажиться.
+pub async fn load_image(src: &str) -> Result<HtmlImageElement, JsValue> {
+    let document = window()
+        .and_then(|w| w.document())
+        .ok_or_else(|| JsValue::from_str("no document"))?;
+    let img = document
+        .create_element("img")?
+        .dyn_into::<HtmlImageElement>()?;
+
+    let ready = ready_promise(&img, HtmlElement::set_onload);
+    img.set_src(src);
+    JsFuture::from(ready).await?;
+    Ok(img)
+}
+
+/// Створює `<audio>` і чекає, поки звук можна буде програти до кінця.
+pub async fn load_sound(src: &str) -> Result<HtmlAudioElement, JsValue> {
+    let audio = HtmlAudioElement::new()?;
+    audio.set_preload("auto");
+
+    let ready = ready_promise(&audio, HtmlElement::set_oncanplaythrough);
+    audio.set_src(src);
+    JsFuture::from(ready).await?;
+    Ok(audio)
+}
+
+/// Проміс, що виконується через обробник `on_ready` і відхиляється через `onerror`.
+/// Обробники ставимо до `src`, щоб не пропустити подію закешованого файлу.
+fn ready_promise(element: &HtmlElement, on_ready: fn(&HtmlElement, Option<&Function>)) -> Promise {
+    Promise::new(&mut |resolve, reject| {
+        let ready = Closure::once_into_js(move || {
+            let _ = resolve.call0(&JsValue::NULL);
+        });
+        let failed = Closure::once_into_js(move || {
+            let _ = reject.call1(&JsValue::NULL, &JsValue::from_str("file failed to load"));
+        });
+        on_ready(element, Some(ready.unchecked_ref()));
+        element.set_onerror(Some(failed.unchecked_ref()));
+    })
+}
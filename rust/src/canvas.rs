@@ -0,0 +1,166 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+use wasm_bindgen::{Clamped, JsCast, JsValue};
+use web_sys::{window, CanvasRenderingContext2d, HtmlCanvasElement, HtmlImageElement, ImageData};
+
+use wararar_core::render::{LayerState, Rect, Renderer};
+
+pub struct CanvasRenderer {
+    ctx: CanvasRenderingContext2d,
+    images: RefCell<HashMap<String, HtmlImageElement>>,
+    layers: RefCell<HashMap<String, (HtmlCanvasElement, CanvasRenderingContext2d)>>,
+    target: RefCell<Option<CanvasRenderingContext2d>>, // шар, у який зараз малюємо
+}
+
+impl CanvasRenderer {
+    pub fn new(ctx: CanvasRenderingContext2d) -> Self {
+        Self {
+            ctx,
+            images: RefCell::new(HashMap::new()),
+            layers: RefCell::new(HashMap::new()),
+            target: RefCell::new(None),
+        }
+    }
+
+    fn ctx(&self) -> CanvasRenderingContext2d {
+        self.target
+            .borrow()
+            .clone()
+            .unwrap_or_else(|| self.ctx.clone())
+    }
+
+    pub fn add_image(&self, key: &str, image: HtmlImageElement) {
+        self.images.borrow_mut().insert(key.to_string(), image);
+    }
+}
+
+impl Renderer for CanvasRenderer {
+    fn clear(&self, width: f64, height: f64) {
+        self.ctx().clear_rect(0.0, 0.0, width, height);
+    }
+
+    fn fill_rect(&self, rect: Rect, color: &str) {
+        let ctx = self.ctx();
+        ctx.set_fill_style_str(color);
+        ctx.fill_rect(rect.x, rect.y, rect.width, rect.height);
+    }
+
+    fn fill_circle(&self, x: f64, y: f64, radius: f64, color: &str) {
+        let ctx = self.ctx();
+        ctx.set_fill_style_str(color);
+        ctx.begin_path();
+        let _ = ctx.arc(x, y, radius, 0.0, PI * 2.0);
+        ctx.fill();
+    }
+
+    fn draw_sprite(&self, sprite: &str, source: Rect, dest: Rect, flip_x: bool) {
+        let images = self.images.borrow();
+        let Some(image) = images.get(sprite) else {
+            return;
+        };
+
+        let ctx = self.ctx();
+        ctx.save();
+        let _ = ctx.translate(dest.x, dest.y);
+        if flip_x {
+            let _ = ctx.translate(dest.width, 0.0);
+            let _ = ctx.scale(-1.0, 1.0);
+        }
+        let _ = ctx.draw_image_with_html_image_element_and_sw_and_sh_and_dx_and_dy_and_dw_and_dh(
+            image,
+            source.x,
+            source.y,
+            source.width,
+            source.height,
+            0.0,
+            0.0,
+            dest.width,
+            dest.height,
+        );
+        ctx.restore();
+    }
+
+    fn push_transform(&self, offset_x: f64, offset_y: f64, scale: f64) {
+        let ctx = self.ctx();
+        ctx.save();
+        let _ = ctx.scale(scale, scale);
+        let _ = ctx.translate(-offset_x, -offset_y);
+    }
+
+    fn pop_transform(&self) {
+        self.ctx().restore();
+    }
+
+    fn begin_layer(&self, layer: &str, width: f64, height: f64) -> LayerState {
+        let (width, height) = (width.ceil() as u32, height.ceil() as u32);
+        let mut layers = self.layers.borrow_mut();
+        let mut state = LayerState::Cached;
+        if !layers
+            .get(layer)
+            .is_some_and(|(c, _)| c.width() == width && c.height() == height)
+        {
+            let Ok((canvas, ctx)) = scratch_canvas(width, height) else {
+                return LayerState::Unsupported;
+            };
+            ctx.set_image_smoothing_enabled(false);
+            layers.insert(layer.to_string(), (canvas, ctx));
+            state = LayerState::Fresh;
+        }
+        *self.target.borrow_mut() = layers.get(layer).map(|(_, ctx)| ctx.clone());
+        state
+    }
+
+    fn end_layer(&self) {
+        *self.target.borrow_mut() = None;
+    }
+
+    fn clear_rect(&self, rect: Rect) {
+        self.ctx()
+            .clear_rect(rect.x, rect.y, rect.width, rect.height);
+    }
+
+    fn draw_layer(&self, layer: &str) {
+        if let Some((canvas, _)) = self.layers.borrow().get(layer) {
+            let _ = self
+                .ctx
+                .draw_image_with_html_canvas_element(canvas, 0.0, 0.0);
+        }
+    }
+}
+
+fn scratch_canvas(
+    width: u32,
+    height: u32,
+) -> Result<(HtmlCanvasElement, CanvasRenderingContext2d), JsValue> {
+    let document = window().unwrap().document().unwrap();
+    let canvas = document
+        .create_element("canvas")?
+        .dyn_into::<HtmlCanvasElement>()?;
+    canvas.set_width(width);
+    canvas.set_height(height);
+    let ctx = canvas
+        .get_context("2d")?
+        .unwrap()
+        .dyn_into::<CanvasRenderingContext2d>()?;
+    Ok((canvas, ctx))
+}
+
+/// Розпаковує картинку в RGBA-байти через тимчасовий canvas.
+pub fn image_pixels(image: &HtmlImageElement) -> Result<(usize, usize, Vec<u8>), JsValue> {
+    let (width, height) = (image.natural_width(), image.natural_height());
+    let (_, ctx) = scratch_canvas(width, height)?;
+    ctx.draw_image_with_html_image_element(image, 0.0, 0.0)?;
+    let data = ctx.get_image_data(0.0, 0.0, width as f64, height as f64)?;
+    Ok((width as usize, height as usize, data.data().0))
+}
+
+/// RGBA-байти -> `data:image/png;base64,...`, щоб віддати файл на збереження.
+pub fn pixels_to_data_url(width: usize, height: usize, rgba: &[u8]) -> Result<String, JsValue> {
+    let (canvas, ctx) = scratch_canvas(width as u32, height as u32)?;
+    let data =
+        ImageData::new_with_u8_clamped_array_and_sh(Clamped(rgba), width as u32, height as u32)?;
+    ctx.put_image_data(&data, 0.0, 0.0)?;
+    canvas.to_data_url()
+}
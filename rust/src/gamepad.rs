@@ -0,0 +1,78 @@
+use std::collections::HashSet;
+
+use wasm_bindgen::JsCast;
+use web_sys::{window, Gamepad, GamepadButton};
+
+use wararar_core::gamepad::{GamepadAssignments, GamepadMapping};
+use wararar_core::input::Action;
+
+struct PadState {
+    index: u32,
+    axes: Vec<f64>,
+    buttons: Vec<bool>,
+}
+
+/// Геймпади з Gamepad API. Підключення й відключення відстежуємо опитуванням
+/// `navigator.getGamepads()` щокадру: події `gamepadconnected` браузери
+/// шлють лише після першого натискання, а опитування все одно потрібне.
+#[derive(Default)]
+pub struct Gamepads {
+    pub mapping: GamepadMapping,
+    assignments: GamepadAssignments,
+    pads: Vec<PadState>,
+}
+
+impl Gamepads {
+    pub fn poll(&mut self) {
+        self.pads = read_gamepads();
+        let connected: Vec<u32> = self.pads.iter().map(|p| p.index).collect();
+
+        let before: Vec<Option<usize>> = connected
+            .iter()
+            .map(|i| self.assignments.team_of(*i))
+            .collect();
+        self.assignments.sync(&connected);
+        for (index, was) in connected.iter().zip(before) {
+            if was.is_none() {
+                if let Some(team) = self.assignments.team_of(*index) {
+                    web_sys::console::log_1(
+                        &format!("🎮 Геймпад {} грає за команду {}", index, team).into(),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Дії з усіх геймпадів, призначених команді.
+    pub fn actions_for_team(&self, team: usize) -> HashSet<Action> {
+        let pads: Vec<u32> = self.assignments.pads_for_team(team).collect();
+        self.pads
+            .iter()
+            .filter(|p| pads.contains(&p.index))
+            .flat_map(|p| self.mapping.actions(&p.axes, &p.buttons))
+            .collect()
+    }
+
+    pub fn team_of(&self, index: u32) -> Option<usize> {
+        self.assignments.team_of(index)
+    }
+}
+
+fn read_gamepads() -> Vec<PadState> {
+    let Some(list) = window().and_then(|w| w.navigator().get_gamepads().ok()) else {
+        return Vec::new();
+    };
+    list.iter()
+        .filter_map(|pad| pad.dyn_into::<Gamepad>().ok())
+        .filter(|pad| pad.connected())
+        .map(|pad| PadState {
+            index: pad.index(),
+            axes: pad.axes().iter().filter_map(|a| a.as_f64()).collect(),
+            buttons: pad
+                .buttons()
+                .iter()
+                .map(|b| b.dyn_into::<GamepadButton>().is_ok_and(|b| b.pressed()))
+                .collect(),
+        })
+        .collect()
+}
@@ -1,24 +1,35 @@
-mod animation;
-
-mod models;
+mod assets;
+mod canvas;
+mod gamepad;
+mod net;
 mod utils;
 
-use std::cell::RefCell;
-use std::rc::Rc;
-
-use crate::models::map::Map;
-use crate::models::player::create_player;
+use std::cell::{Cell, RefCell};
 
 use js_sys::Array;
-use std::collections::HashSet;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::{closure::Closure, JsCast};
 use web_sys::{
     window, CanvasRenderingContext2d, ErrorEvent, Event, HtmlCanvasElement, MessageEvent, WebSocket,
 };
 
-use crate::models::game::Game;
-use crate::models::player;
+use wararar_core::assets::{character_key, AssetManifest, LoadProgress, CHARACTER_COLORS};
+use wararar_core::input::{Action, KeyBindings};
+use wararar_core::models::game::Game;
+use wararar_core::models::map::Map;
+use wararar_core::models::map_file::{self, MapFile};
+use wararar_core::models::player::{self, Player};
+use wararar_core::models::position::Position;
+use wararar_core::models::projectile::WeaponKind;
+use wararar_core::models::terrain::TerrainPreset;
+use wararar_core::models::turn::{self, TurnPhase};
+use wararar_core::protocol::{self, Message};
+use wararar_core::tileset::TileSet;
+
+use crate::assets::{load_image, Assets};
+use crate::canvas::{image_pixels, pixels_to_data_url, CanvasRenderer};
+use crate::gamepad::Gamepads;
+use crate::net::Network;
 
 #[wasm_bindgen]
 extern "C" {
@@ -26,11 +37,31 @@ extern "C" {
 }
 
 thread_local! {
-    static GAME: RefCell<Option<Game>> = RefCell::new(None);
+    static GAME: RefCell<Option<Game>> = const { RefCell::new(None) };
+    static NET: RefCell<Option<Network>> = const { RefCell::new(None) };
+    static RENDERER: RefCell<Option<CanvasRenderer>> = const { RefCell::new(None) };
+    static LAST_FRAME_MS: Cell<Option<f64>> = const { Cell::new(None) };
+    static BINDINGS: RefCell<KeyBindings> = RefCell::new(KeyBindings::default());
+    static GAMEPADS: RefCell<Option<Gamepads>> = const { RefCell::new(None) };
+    static DRAW_MS: Cell<f64> = const { Cell::new(0.0) };
+    static ASSETS: RefCell<Assets> = RefCell::new(Assets::new());
+    static TEAM_SETUP: RefCell<Vec<TeamChoice>> = const { RefCell::new(Vec::new()) };
 }
 
+#[derive(Clone)]
+struct TeamChoice {
+    name: String,
+    color: String,
+}
+
+// Світ однаковий для всіх клієнтів незалежно від розміру вікна; камера показує його частину.
+const WORLD_WIDTH: f64 = 2400.0;
+const WORLD_HEIGHT: f64 = 1200.0;
+
 #[wasm_bindgen]
 pub async fn play() -> Result<(), JsValue> {
+    utils::set_panic_hook();
+
     let window = window().unwrap();
     let document = window.document().unwrap();
     let canvas = document
@@ -50,25 +81,58 @@ pub async fn play() -> Result<(), JsValue> {
     ctx.clear_rect(0.0, 0.0, canvas.width() as f64, canvas.height() as f64);
 
     GAME.with(|game| {
-        *game.borrow_mut() = Some(Game::new(canvas_width, canvas_height, Rc::new(ctx)));
+        *game.borrow_mut() = Some(Game::with_world(
+            WORLD_WIDTH,
+            WORLD_HEIGHT,
+            canvas_width,
+            canvas_height,
+        ));
+    });
+    // якщо JS не викликав preload_assets, вантажимо тут; готове береться з кешу
+    preload_assets(None).await?;
+    let renderer = CanvasRenderer::new(ctx);
+    ASSETS.with(|assets| {
+        for (key, image) in assets.borrow().images() {
+            renderer.add_image(key, image.clone());
+        }
+    });
+    RENDERER.with(|r| {
+        *r.borrow_mut() = Some(renderer);
     });
 
-    let player = create_player().await?;
-
-    GAME.with(|game| {
+    // без create_team — дві команди з першими вільними кольорами
+    let teams = TEAM_SETUP.with(|setup| setup.borrow().clone());
+    GAME.with(|game| -> Result<(), JsValue> {
         if let Some(g) = &mut *game.borrow_mut() {
-            g.add_player(player);
-            g.draw();
+            g.tileset = Some(TileSet::jungle());
+            if teams.is_empty() {
+                g.add_player(create_player());
+                g.add_player(create_player());
+            }
+            for choice in &teams {
+                let team = g
+                    .create_team(&choice.name, &choice.color)
+                    .map_err(|e| JsValue::from_str(&e.to_string()))?;
+                g.add_player_to_team(team, create_player());
+            }
+            g.place_players_at_spawns();
+            draw_game(g);
         }
-    });
+        Ok(())
+    })?;
 
     // 🌐 Підключення WebSocket
     let ws = WebSocket::new("ws://127.0.0.1:3000/ws")?; // заміни IP, якщо потрібно
 
     // 📩 Обробка вхідних повідомлень
     let onmessage_callback = Closure::wrap(Box::new(move |e: MessageEvent| {
-        if let Ok(txt) = e.data().dyn_into::<js_sys::JsString>() {
-            web_sys::console::log_1(&format!("📨 Повідомлення від сервера: {}", txt).into());
+        if let Some(txt) = e.data().as_string() {
+            match protocol::decode(&txt) {
+                Ok(message) => handle_server_message(message),
+                Err(err) => web_sys::console::log_1(
+                    &format!("📨 Невідоме повідомлення від сервера: {}", err).into(),
+                ),
+            }
         }
     }) as Box<dyn FnMut(_)>);
     ws.set_onmessage(Some(onmessage_callback.as_ref().unchecked_ref()));
@@ -84,13 +148,140 @@ pub async fn play() -> Result<(), JsValue> {
     // 🔗 Обробка відкриття з'єднання
     let onopen_callback = Closure::wrap(Box::new(move |_: Event| {
         web_sys::console::log_1(&"✅ WebSocket з'єднано!".into());
+        NET.with(|net| {
+            if let Some(n) = &*net.borrow() {
+                n.join();
+            }
+        });
     }) as Box<dyn FnMut(_)>);
     ws.set_onopen(Some(onopen_callback.as_ref().unchecked_ref()));
     onopen_callback.forget();
 
+    let network = match teams.first() {
+        Some(choice) => Network::new(ws, &choice.name, Some(&choice.color)),
+        None => Network::new(ws, "player", None),
+    };
+    NET.with(|net| {
+        *net.borrow_mut() = Some(network);
+    });
+
     Ok(())
 }
 
+/// Завантажує картинки й звуки з маніфесту гри, яких ще немає в кеші.
+/// `on_progress(loaded, total)` викликається на старті й після кожного файлу —
+/// для смуги завантаження. Помилка називає ключ і шлях файлу, що не завантажився.
+#[wasm_bindgen]
+pub async fn preload_assets(on_progress: Option<js_sys::Function>) -> Result<(), JsValue> {
+    let manifest = AssetManifest::game();
+    manifest
+        .validate()
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    let report = |progress: LoadProgress| -> Result<(), JsValue> {
+        if let Some(callback) = &on_progress {
+            callback.call2(
+                &JsValue::NULL,
+                &(progress.loaded as u32).into(),
+                &(progress.total as u32).into(),
+            )?;
+        }
+        Ok(())
+    };
+
+    let pending = ASSETS.with(|assets| assets.borrow().pending(&manifest));
+    report(ASSETS.with(|assets| assets.borrow().progress(&manifest)))?;
+    for entry in pending {
+        let asset = assets::load(&entry)
+            .await
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        ASSETS.with(|assets| assets.borrow_mut().insert(&entry.key, asset));
+        report(ASSETS.with(|assets| assets.borrow().progress(&manifest)))?;
+    }
+    Ok(())
+}
+
+/// Команда для наступного `play()` з назвою і кольором з `character_colors()`.
+/// Повертає індекс команди; колір, який уже взяла інша команда, — помилка.
+/// Перша команда — та, якою клієнт грає по мережі.
+#[wasm_bindgen]
+pub fn create_team(name: &str, color: &str) -> Result<usize, JsValue> {
+    TEAM_SETUP.with(|setup| {
+        let mut setup = setup.borrow_mut();
+        turn::check_color(color, setup.iter().map(|t| t.color.as_str()))
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        setup.push(TeamChoice {
+            name: name.to_string(),
+            color: color.to_string(),
+        });
+        Ok(setup.len() - 1)
+    })
+}
+
+/// Забуває команди, створені через `create_team`.
+#[wasm_bindgen]
+pub fn clear_teams() {
+    TEAM_SETUP.with(|setup| setup.borrow_mut().clear());
+}
+
+/// Кольори, з яких можна вибирати команді.
+#[wasm_bindgen]
+pub fn character_colors() -> Array {
+    CHARACTER_COLORS
+        .iter()
+        .copied()
+        .map(JsValue::from)
+        .collect()
+}
+
+fn create_player() -> Player {
+    // справжнє місце вибере place_players_at_spawns, колір — команда
+    Player::new(
+        Position::new(0.0, 0.0),
+        Some(player::character_animation(&character_key(
+            CHARACTER_COLORS[0],
+        ))),
+    )
+}
+
+fn draw_game(g: &mut Game) {
+    let performance = window().and_then(|w| w.performance());
+    let started = performance.as_ref().map_or(0.0, |p| p.now());
+    RENDERER.with(|renderer| {
+        if let Some(r) = &*renderer.borrow() {
+            g.draw(r);
+        }
+    });
+    if let Some(p) = performance {
+        // ковзне середнє, щоб число в HUD не стрибало
+        DRAW_MS.with(|ms| ms.set(ms.get() * 0.9 + (p.now() - started) * 0.1));
+    }
+}
+
+/// Середній час малювання кадру в мілісекундах.
+#[wasm_bindgen]
+pub fn draw_time_ms() -> f64 {
+    DRAW_MS.with(Cell::get)
+}
+
+fn handle_server_message(message: Message) {
+    GAME.with(|game| {
+        NET.with(|net| {
+            if let (Some(g), Some(n)) = (&mut *game.borrow_mut(), &mut *net.borrow_mut()) {
+                n.handle_message(g, message);
+            }
+        });
+    });
+}
+
+fn local_turn(g: &Game) -> bool {
+    NET.with(|net| {
+        net.borrow()
+            .as_ref()
+            .is_none_or(|n| n.controls(g.turns.current_team()))
+    })
+}
+
 #[wasm_bindgen]
 pub fn draw() -> Result<(), JsValue> {
     let window = window().unwrap();
@@ -112,30 +303,112 @@ pub fn draw() -> Result<(), JsValue> {
     Ok(())
 }
 
+/// `timestamp` — час кадру в мілісекундах, як його дає `requestAnimationFrame`.
 #[wasm_bindgen]
-pub fn update(pressed_keys: Array) -> Result<(), JsValue> {
+pub fn update(pressed_keys: Array, timestamp: f64) -> Result<(), JsValue> {
+    let elapsed = LAST_FRAME_MS.with(|last| {
+        let elapsed = last.get().map_or(0.0, |prev| (timestamp - prev) / 1000.0);
+        last.set(Some(timestamp));
+        elapsed
+    });
+
     GAME.with(|game| {
         if let Some(g) = &mut *game.borrow_mut() {
-            if let Some(player) = g.get_current_player_mut() {
-                let mut keys_set = HashSet::new();
-                for key in pressed_keys.iter() {
-                    if let Some(s) = key.as_string() {
-                        keys_set.insert(s);
+            let local = local_turn(g);
+            g.follow_remote_turns = !local;
+
+            if local {
+                let keys: Vec<String> = pressed_keys.iter().filter_map(|k| k.as_string()).collect();
+                let mut actions =
+                    BINDINGS.with(|b| b.borrow().actions(keys.iter().map(String::as_str)));
+                // клавіатура керує будь-якою командою, геймпад — лише своєю
+                if let Some(team) = g.turns.current_team() {
+                    GAMEPADS.with(|pads| {
+                        let mut pads = pads.borrow_mut();
+                        let pads = pads.get_or_insert_with(Gamepads::default);
+                        pads.poll();
+                        actions.extend(pads.actions_for_team(team));
+                    });
+                }
+                let mut sorted: Vec<Action> = actions.iter().copied().collect();
+                sorted.sort_by_key(|a| a.name());
+                NET.with(|net| {
+                    if let Some(n) = &mut *net.borrow_mut() {
+                        n.send_input_if_changed(&sorted);
                     }
+                });
+                if let Some(player) = g.get_current_player_mut() {
+                    player.set_actions(actions);
                 }
-                player.set_pressed_keys(keys_set);
             }
-            g.update();
-            g.draw();
+
+            let steps = g.update(elapsed);
+            let events = g.take_events();
+            if local {
+                NET.with(|net| {
+                    if let Some(n) = &mut *net.borrow_mut() {
+                        n.after_local_update(g, &events, steps);
+                    }
+                });
+            }
+            draw_game(g);
         }
     });
 
     Ok(())
 }
 
+fn parse_action(name: &str) -> Result<Action, JsValue> {
+    Action::from_name(name).ok_or_else(|| JsValue::from_str(&format!("Unknown action: {}", name)))
+}
+
+/// Прив'язує клавішу (`KeyboardEvent.code`) до дії, напр. `bind_key("KeyJ", "fire")`.
+#[wasm_bindgen]
+pub fn bind_key(key: &str, action: &str) -> Result<(), JsValue> {
+    let action = parse_action(action)?;
+    BINDINGS.with(|b| b.borrow_mut().bind(key, action));
+    Ok(())
+}
+
+#[wasm_bindgen]
+pub fn unbind_key(key: &str) {
+    BINDINGS.with(|b| b.borrow_mut().unbind(key));
+}
+
+#[wasm_bindgen]
+pub fn reset_key_bindings() {
+    BINDINGS.with(|b| *b.borrow_mut() = KeyBindings::default());
+}
+
+/// Клавіші, прив'язані до дії, — щоб показати їх у меню налаштувань.
+#[wasm_bindgen]
+pub fn keys_for_action(action: &str) -> Result<Array, JsValue> {
+    let action = parse_action(action)?;
+    let keys = BINDINGS.with(|b| b.borrow().keys_for(action));
+    Ok(keys.into_iter().map(JsValue::from).collect())
+}
+
+/// Мертва зона стіків геймпада, 0.0..1.0.
+#[wasm_bindgen]
+pub fn set_gamepad_dead_zone(dead_zone: f64) {
+    GAMEPADS.with(|pads| {
+        pads.borrow_mut()
+            .get_or_insert_with(Gamepads::default)
+            .mapping
+            .dead_zone = dead_zone.clamp(0.0, 1.0);
+    });
+}
+
+/// За яку команду грає геймпад з індексом `index`. `undefined`, якщо він не підключений.
+#[wasm_bindgen]
+pub fn gamepad_team(index: u32) -> Option<usize> {
+    GAMEPADS.with(|pads| pads.borrow().as_ref()?.team_of(index))
+}
+
 #[wasm_bindgen]
 pub async fn init_player() -> Result<(), JsValue> {
-    let player = crate::player::create_player().await?;
+    preload_assets(None).await?;
+    let player = create_player();
 
     GAME.with(|game| {
         if let Some(ref mut g) = *game.borrow_mut() {
@@ -145,13 +418,264 @@ pub async fn init_player() -> Result<(), JsValue> {
     Ok(())
 }
 
+#[wasm_bindgen]
+pub fn fire(weapon_name: &str, angle_degrees: f64, power: f64) -> Result<(), JsValue> {
+    let weapon = WeaponKind::from_name(weapon_name)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown weapon: {}", weapon_name)))?;
+    GAME.with(|game| {
+        if let Some(g) = &mut *game.borrow_mut() {
+            // іншим клієнтам постріл піде з `GameEvent::ShotFired`
+            if local_turn(g) {
+                g.fire(weapon, angle_degrees.to_radians(), power);
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Нова процедурна карта, напр. `new_map(42n, "islands")`.
+#[wasm_bindgen]
+pub fn new_map(seed: u64, preset: &str) -> Result<(), JsValue> {
+    let preset = TerrainPreset::from_name(preset)
+        .ok_or_else(|| JsValue::from_str(&format!("Unknown terrain preset: {}", preset)))?;
+    GAME.with(|game| {
+        if let Some(g) = &mut *game.borrow_mut() {
+            let (width, height) = (g.map.width(), g.map.height());
+            g.set_map(Map::generate(width, height, seed, preset));
+            g.place_players_at_spawns();
+        }
+    });
+    Ok(())
+}
+
+fn replace_map(map: Map) {
+    GAME.with(|game| {
+        if let Some(g) = &mut *game.borrow_mut() {
+            g.set_map(map);
+            g.place_players_at_spawns();
+        }
+    });
+}
+
+/// Карта з JSON-файлу (формат описано в `wararar_core::models::map_file`).
+#[wasm_bindgen]
+pub fn load_map_json(text: &str) -> Result<(), JsValue> {
+    let map = MapFile::parse(text)
+        .and_then(MapFile::into_map)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    replace_map(map);
+    Ok(())
+}
+
+/// Карта з PNG-маски: кожен непрозорий піксель — тайл землі розміром `tile_size`.
+#[wasm_bindgen]
+pub async fn load_map_image(src: String, tile_size: f64) -> Result<(), JsValue> {
+    let image = load_image(&src).await?;
+    let (width, height, rgba) = image_pixels(&image)?;
+    let map = map_file::from_alpha_mask(width, height, &rgba, tile_size)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    replace_map(map);
+    Ok(())
+}
+
+/// Поточна карта разом з кратерами у форматі JSON.
+#[wasm_bindgen]
+pub fn export_map_json(name: &str) -> Option<String> {
+    GAME.with(|game| {
+        let game = game.borrow();
+        Some(MapFile::from_map(&game.as_ref()?.map, name).to_json())
+    })
+}
+
+/// Поточна карта як PNG-маска (`data:` URL).
+#[wasm_bindgen]
+pub fn export_map_png() -> Result<Option<String>, JsValue> {
+    GAME.with(|game| {
+        let game = game.borrow();
+        let Some(g) = game.as_ref() else {
+            return Ok(None);
+        };
+        let rgba = map_file::to_alpha_mask(&g.map);
+        pixels_to_data_url(g.map.cols(), g.map.rows(), &rgba).map(Some)
+    })
+}
+
+/// Колесо миші над полотном: `delta_y` як у `WheelEvent.deltaY` (від'ємне — наблизити).
+#[wasm_bindgen]
+pub fn zoom_camera(screen_x: f64, screen_y: f64, delta_y: f64) {
+    GAME.with(|game| {
+        if let Some(g) = &mut *game.borrow_mut() {
+            let factor = (-delta_y * 0.001).exp();
+            g.camera.zoom_at(&Position::new(screen_x, screen_y), factor);
+        }
+    });
+}
+
+/// Де миша на полотні — біля країв камера прокручується. `clear_pointer`, коли миша пішла.
+#[wasm_bindgen]
+pub fn set_pointer(screen_x: f64, screen_y: f64) {
+    GAME.with(|game| {
+        if let Some(g) = &mut *game.borrow_mut() {
+            g.camera
+                .set_pointer(Some(Position::new(screen_x, screen_y)));
+        }
+    });
+}
+
+#[wasm_bindgen]
+pub fn clear_pointer() {
+    GAME.with(|game| {
+        if let Some(g) = &mut *game.borrow_mut() {
+            g.camera.set_pointer(None);
+        }
+    });
+}
+
+/// Точка полотна -> `[x, y]` у світі, напр. щоб вибух з'явився під курсором.
+#[wasm_bindgen]
+pub fn screen_to_world(screen_x: f64, screen_y: f64) -> Vec<f64> {
+    GAME.with(|game| match &*game.borrow() {
+        Some(g) => {
+            let world = g.camera.screen_to_world(&Position::new(screen_x, screen_y));
+            vec![world.x, world.y]
+        }
+        None => vec![screen_x, screen_y],
+    })
+}
+
+#[wasm_bindgen]
+pub fn set_wind(wind: f64) -> Result<(), JsValue> {
+    GAME.with(|game| {
+        if let Some(g) = &mut *game.borrow_mut() {
+            g.wind = wind;
+        }
+    });
+    Ok(())
+}
+
+/// `callback(x, y, radius)` викликається при кожному вибуху снаряда.
+#[wasm_bindgen]
+pub fn on_detonation(callback: js_sys::Function) -> Result<(), JsValue> {
+    GAME.with(|game| {
+        if let Some(g) = &mut *game.borrow_mut() {
+            g.set_detonation_callback(move |d| {
+                let _ = callback.call3(
+                    &JsValue::NULL,
+                    &JsValue::from_f64(d.x),
+                    &JsValue::from_f64(d.y),
+                    &JsValue::from_f64(d.radius),
+                );
+            });
+        }
+    });
+    Ok(())
+}
+
+#[wasm_bindgen(getter_with_clone)]
+pub struct TurnInfo {
+    pub team: usize,
+    pub team_name: String,
+    pub player: usize,
+    pub seconds_left: f64,
+    pub retreating: bool,
+}
+
+/// Чий зараз хід і скільки секунд лишилось. `undefined`, якщо гравців ще немає.
+#[wasm_bindgen]
+pub fn turn_info() -> Option<TurnInfo> {
+    GAME.with(|game| {
+        let game = game.borrow();
+        let turns = &game.as_ref()?.turns;
+        let team = turns.current_team()?;
+        Some(TurnInfo {
+            team,
+            team_name: turns.teams[team].name.clone(),
+            player: turns.current_player()?,
+            seconds_left: turns.seconds_left(),
+            retreating: turns.phase() == TurnPhase::Retreat,
+        })
+    })
+}
+
+#[wasm_bindgen]
+pub fn set_turn_time(turn_seconds: f64, retreat_seconds: f64) -> Result<(), JsValue> {
+    GAME.with(|game| {
+        if let Some(g) = &mut *game.borrow_mut() {
+            g.turns.turn_time = turn_seconds;
+            g.turns.retreat_time = retreat_seconds;
+        }
+    });
+    Ok(())
+}
+
+#[wasm_bindgen]
+pub fn end_turn() -> Result<(), JsValue> {
+    GAME.with(|game| {
+        if let Some(g) = &mut *game.borrow_mut() {
+            // чужий хід завершує лише його власник
+            if !local_turn(g) {
+                return;
+            }
+            g.end_turn();
+            // надсилаємо одразу: з наступного кадру хід уже може бути чужим,
+            // і `after_local_update` його не побачить
+            let (Some(team), Some(player)) = (g.turns.current_team(), g.turns.current_player())
+            else {
+                return;
+            };
+            NET.with(|net| {
+                if let Some(n) = &*net.borrow() {
+                    n.send_turn_change(g, team, player);
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+#[wasm_bindgen]
+pub fn send_chat(text: &str) -> Result<(), JsValue> {
+    NET.with(|net| {
+        if let Some(n) = &*net.borrow() {
+            n.send(Message::Chat {
+                client_id: n.client_id.unwrap_or_default(),
+                text: text.to_string(),
+            });
+        }
+    });
+    Ok(())
+}
+
+/// `callback(clientId, text)` викликається для кожного повідомлення чату.
+#[wasm_bindgen]
+pub fn on_chat(callback: js_sys::Function) -> Result<(), JsValue> {
+    NET.with(|net| {
+        if let Some(n) = &mut *net.borrow_mut() {
+            n.chat_callback = Some(callback);
+        }
+    });
+    Ok(())
+}
+
+#[wasm_bindgen]
+pub fn leave() -> Result<(), JsValue> {
+    NET.with(|net| {
+        if let Some(n) = net.borrow_mut().take() {
+            n.send(Message::Leave {
+                client_id: n.client_id.unwrap_or_default(),
+            });
+            n.close();
+        }
+    });
+    Ok(())
+}
+
+/// Новий розмір полотна. Світ не змінюється — лише видима його частина.
 #[wasm_bindgen]
 pub fn resize(width: f64, height: f64) -> Result<(), JsValue> {
     GAME.with(|game| {
         if let Some(g) = &mut *game.borrow_mut() {
-            g.canvas_width = width;
-            g.canvas_height = height;
-            g.map = Map::new(width, height, Rc::clone(&g.canvas));
+            g.resize_viewport(width, height);
         }
     });
     Ok(())
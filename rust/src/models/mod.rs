@@ -1,5 +0,0 @@
-pub mod player;
-pub mod map;
-pub mod game;
-pub mod position;
-pub mod traits;
\ No newline at end of file
@@ -1,19 +0,0 @@
-pub(crate) struct Position {
-    pub(crate) x: f64,
-    pub(crate) y: f64,
-}
-
-impl Position {
-    pub fn new(x: f64, y: f64) -> Self {
-        Self { x, y }
-    }
-
-    pub fn move_by(&mut self, dx: f64, dy: f64) {
-        self.x += dx;
-        self.y += dy;
-    }
-
-    pub fn distance_to(&self, other: &Position) -> f64 {
-        ((self.x - other.x).powi(2) + (self.y - other.y).powi(2)).sqrt()
-    }
-}
@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+
+use wasm_bindgen::JsValue;
+use web_sys::WebSocket;
+
+use wararar_core::input::Action;
+use wararar_core::models::event::GameEvent;
+use wararar_core::models::game::Game;
+use wararar_core::models::map::Map;
+use wararar_core::models::terrain::TerrainPreset;
+use wararar_core::protocol::{self, FireInput, Message};
+
+const SNAPSHOT_EVERY_TICKS: u64 = 6; // кроків симуляції, тобто ~10 знімків на секунду
+
+pub struct Network {
+    socket: WebSocket,
+    pub client_id: Option<u32>,
+    pub slot: Option<usize>,
+    peers: HashMap<u32, usize>, // client_id -> slot
+    team_name: String,
+    team_color: Option<String>, // бажаний; справжній призначить сервер
+    last_actions: Vec<Action>,
+    tick: u64,
+    pub chat_callback: Option<js_sys::Function>,
+}
+
+impl Network {
+    /// `team_name` і `team_color` — якою командою клієнт хоче грати.
+    pub fn new(socket: WebSocket, team_name: &str, team_color: Option<&str>) -> Self {
+        Self {
+            socket,
+            client_id: None,
+            slot: None,
+            peers: HashMap::new(),
+            team_name: team_name.to_string(),
+            team_color: team_color.map(str::to_string),
+            last_actions: Vec::new(),
+            tick: 0,
+            chat_callback: None,
+        }
+    }
+
+    pub fn send(&self, message: Message) {
+        if self.socket.ready_state() != WebSocket::OPEN {
+            return;
+        }
+        if let Err(e) = self.socket.send_with_str(&protocol::encode(message)) {
+            web_sys::console::log_1(&format!("❌ Не вдалося надіслати: {:?}", e).into());
+        }
+    }
+
+    pub fn join(&self) {
+        self.send(Message::Join {
+            client_id: 0,
+            slot: 0,
+            name: self.team_name.clone(),
+            color: self.team_color.clone(),
+        });
+    }
+
+    pub fn close(&self) {
+        let _ = self.socket.close();
+    }
+
+    /// Чи керує цей клієнт командою `team`. Поки сервер не призначив слот — граємо
+    /// локально за всіх; команди без власника веде клієнт зі слотом 0.
+    pub fn controls(&self, team: Option<usize>) -> bool {
+        let (Some(slot), Some(team)) = (self.slot, team) else {
+            return true;
+        };
+        slot == team || (slot == 0 && !self.peers.values().any(|&s| s == team))
+    }
+
+    pub fn send_input_if_changed(&mut self, actions: &[Action]) {
+        if actions == self.last_actions.as_slice() {
+            return;
+        }
+        self.last_actions = actions.to_vec();
+        self.send(Message::Input {
+            client_id: self.client_id.unwrap_or_default(),
+            actions: self.last_actions.clone(),
+            fire: None,
+        });
+    }
+
+    pub fn send_fire(&self, fire: FireInput) {
+        self.send(Message::Input {
+            client_id: self.client_id.unwrap_or_default(),
+            actions: self.last_actions.clone(),
+            fire: Some(fire),
+        });
+    }
+
+    pub fn send_turn_change(&self, game: &Game, team: usize, player: usize) {
+        self.send(Message::TurnChange {
+            team,
+            player,
+            seconds_left: game.turns.seconds_left(),
+        });
+    }
+
+    /// Викликається після `Game::update`, коли хід наш; `steps` — скільки
+    /// кроків симуляції щойно виконано.
+    pub fn after_local_update(&mut self, game: &Game, events: &[GameEvent], steps: usize) {
+        let previous_tick = self.tick;
+        self.tick += steps as u64;
+
+        for event in events {
+            match *event {
+                GameEvent::TurnChanged { team, player } => {
+                    self.send_turn_change(game, team, player);
+                }
+                GameEvent::ShotFired {
+                    weapon,
+                    angle,
+                    power,
+                } => self.send_fire(FireInput {
+                    weapon: weapon.name().to_string(),
+                    angle,
+                    power,
+                }),
+                _ => {}
+            }
+        }
+
+        if self.tick / SNAPSHOT_EVERY_TICKS != previous_tick / SNAPSHOT_EVERY_TICKS {
+            self.send(Message::Snapshot {
+                tick: self.tick,
+                players: game.snapshot(),
+            });
+        }
+    }
+
+    pub fn handle_message(&mut self, game: &mut Game, message: Message) {
+        match message {
+            Message::Welcome {
+                client_id,
+                slot,
+                color,
+                seed,
+                preset,
+            } => {
+                self.client_id = Some(client_id);
+                self.slot = Some(slot);
+                assign_team(game, slot, &self.team_name, &color);
+                let preset = TerrainPreset::from_name(&preset).unwrap_or_default();
+                let (width, height) = (game.map.width(), game.map.height());
+                game.set_map(Map::generate(width, height, seed, preset));
+                game.place_players_at_spawns();
+            }
+            Message::Join {
+                client_id,
+                slot,
+                name,
+                color,
+            } => {
+                self.peers.insert(client_id, slot);
+                if let Some(color) = color {
+                    assign_team(game, slot, &name, &color);
+                }
+            }
+            Message::Leave { client_id } => {
+                self.peers.remove(&client_id);
+            }
+            Message::Input { actions, fire, .. } => {
+                if !self.controls(game.turns.current_team()) {
+                    game.apply_remote_input(actions, fire);
+                }
+            }
+            Message::Snapshot { players, .. } => {
+                if !self.controls(game.turns.current_team()) {
+                    game.apply_snapshot(&players);
+                }
+            }
+            Message::TurnChange { team, player, .. } => {
+                game.force_turn(team, player);
+            }
+            Message::Chat { client_id, text } => {
+                if let Some(callback) = &self.chat_callback {
+                    let _ = callback.call2(
+                        &JsValue::NULL,
+                        &JsValue::from_f64(client_id as f64),
+                        &JsValue::from_str(&text),
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn assign_team(game: &mut Game, slot: usize, name: &str, color: &str) {
+    if slot >= game.turns.teams.len() {
+        return; // у цьому матчі менше команд, ніж гравців у лобі
+    }
+    if let Err(e) = game.assign_team(slot, name, color) {
+        web_sys::console::log_1(&format!("⚠️ Команда {}: {}", slot, e).into());
+    }
+}